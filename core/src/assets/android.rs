@@ -0,0 +1,167 @@
+// Copyright (c) 2023, BlockProject 3D
+//
+// All rights reserved.
+//
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+//
+//     * Redistributions of source code must retain the above copyright notice,
+//       this list of conditions and the following disclaimer.
+//     * Redistributions in binary form must reproduce the above copyright notice,
+//       this list of conditions and the following disclaimer in the documentation
+//       and/or other materials provided with the distribution.
+//     * Neither the name of BlockProject 3D nor the names of its contributors
+//       may be used to endorse or promote products derived from this software
+//       without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS
+// "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT
+// LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+// CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+// EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+// PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+// PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+// LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+// NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+// SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use jni::objects::JObject;
+use jni::JavaVM;
+use ndk_sys::{
+    AAssetDir_close, AAssetDir_getNextFileName, AAssetManager, AAssetManager_fromJava,
+    AAssetManager_open, AAssetManager_openDir, AAsset_close, AAsset_getLength64, AAsset_read,
+    AASSET_MODE_BUFFER,
+};
+use std::ffi::{CStr, CString};
+use std::io::{Error, ErrorKind, Result};
+use std::path::{Path, PathBuf};
+
+pub fn get_exe_path() -> Option<PathBuf> {
+    //Android applications are not started from an executable file.
+    None
+}
+
+pub fn get_resources_dir() -> Option<PathBuf> {
+    //Assets packaged in the APK are not real files.
+    None
+}
+
+fn with_asset_manager<R, F: FnOnce(*mut AAssetManager) -> Option<R>>(f: F) -> Option<R> {
+    //android_context panics when no context was initialized, ex: a native binary started from a
+    // shell, in which case there is no asset manager.
+    //This cannot be caught when built with panic = "abort": the process aborts instead.
+    let ctx = std::panic::catch_unwind(ndk_context::android_context).ok()?;
+    let vm = unsafe { JavaVM::from_raw(ctx.vm() as _) }.ok()?;
+    let mut env = vm.attach_current_thread().ok()?;
+    let context = unsafe { JObject::from_raw(ctx.context() as _) };
+    //The thread may already be attached, in which case local references are only freed when
+    // the thread returns to java, so use a local frame to free them as soon as f returns.
+    let res: jni::errors::Result<Option<R>> = env.with_local_frame(4, |env| {
+        let assets = match env.call_method(&context, "getAssets", "()Landroid/content/res/AssetManager;", &[]) {
+            Ok(v) => v.l()?,
+            Err(_) => {
+                //Do not leave a pending java exception behind.
+                env.exception_clear()?;
+                return Ok(None);
+            }
+        };
+        //The AAssetManager is only valid as long as the java AssetManager is referenced, so keep
+        // `assets` alive until f returns.
+        let manager = unsafe { AAssetManager_fromJava(env.get_raw() as _, assets.as_raw() as _) };
+        if manager.is_null() {
+            return Ok(None);
+        }
+        Ok(f(manager))
+    });
+    res.ok().flatten()
+}
+
+fn read_all<F: FnMut(&mut [u8]) -> isize>(len: usize, mut read: F) -> Result<Vec<u8>> {
+    let mut buf = vec![0; len];
+    let mut pos = 0;
+    while pos < len {
+        let res = read(&mut buf[pos..]);
+        if res <= 0 {
+            return Err(Error::new(ErrorKind::UnexpectedEof, "failed to read asset"));
+        }
+        pos += res as usize;
+    }
+    Ok(buf)
+}
+
+fn collect_files<F: FnMut() -> Option<String>>(subdir: &str, mut next: F) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    while let Some(file) = next() {
+        files.push(Path::new(subdir).join(file));
+    }
+    files
+}
+
+pub fn read_asset(file_name: &str) -> Option<Result<Vec<u8>>> {
+    let name = match CString::new(file_name) {
+        Ok(v) => v,
+        Err(e) => return Some(Err(Error::new(ErrorKind::InvalidInput, e))),
+    };
+    with_asset_manager(|manager| unsafe {
+        let asset = AAssetManager_open(manager, name.as_ptr(), AASSET_MODE_BUFFER as _);
+        if asset.is_null() {
+            return None;
+        }
+        let len = AAsset_getLength64(asset) as usize;
+        let res = read_all(len, |buf| AAsset_read(asset, buf.as_mut_ptr() as _, buf.len()) as isize);
+        AAsset_close(asset);
+        Some(res)
+    })
+}
+
+pub fn list_assets(subdir: &str) -> Option<Vec<PathBuf>> {
+    let name = CString::new(subdir).ok()?;
+    with_asset_manager(|manager| unsafe {
+        let dir = AAssetManager_openDir(manager, name.as_ptr());
+        if dir.is_null() {
+            return None;
+        }
+        let files = collect_files(subdir, || {
+            let file = AAssetDir_getNextFileName(dir);
+            match file.is_null() {
+                true => None,
+                false => Some(CStr::from_ptr(file).to_string_lossy().into_owned())
+            }
+        });
+        AAssetDir_close(dir);
+        Some(files)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{collect_files, read_all};
+    use std::path::PathBuf;
+
+    #[test]
+    fn read_chunks() {
+        //Mock asset returning its content in chunks of at most 3 bytes.
+        let data = b"hello world";
+        let mut pos = 0;
+        let buf = read_all(data.len(), |buf| {
+            let len = buf.len().min(3);
+            buf[..len].copy_from_slice(&data[pos..pos + len]);
+            pos += len;
+            len as isize
+        }).unwrap();
+        assert_eq!(buf, data);
+        assert!(read_all(4, |_| -1).is_err());
+        assert!(read_all(4, |_| 0).is_err());
+        assert!(read_all(0, |_| unreachable!()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn list_files() {
+        //Mock asset directory.
+        let mut names = vec!["b.txt", "a.txt"];
+        let files = collect_files("dir", || names.pop().map(String::from));
+        assert_eq!(files, vec![PathBuf::from("dir/a.txt"), PathBuf::from("dir/b.txt")]);
+        assert!(collect_files("dir", || None).is_empty());
+    }
+}
@@ -29,7 +29,7 @@
 use libc::{strlen, PATH_MAX};
 use std::ffi::{c_char, c_int, OsStr};
 use std::os::unix::ffi::OsStrExt;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use objc::{class, msg_send, sel, sel_impl};
 use objc::runtime::Object;
 use objc_foundation::{INSString, NSString};
@@ -45,13 +45,15 @@ pub fn get_exe_path() -> Option<PathBuf> {
         let res = _NSGetExecutablePath(&mut buf as _, &mut size as _);
         if res == -1 {
             //path is too large
-            let mut v = Vec::with_capacity(size as usize);
+            let mut v: Vec<c_char> = Vec::with_capacity(size as usize);
             let res = _NSGetExecutablePath(v.as_mut_ptr(), &mut size as _);
             if res != 0 {
                 //Something really bad happened.
                 return None;
             }
-            let str = OsStr::from_bytes(std::mem::transmute(&v[..size as usize]));
+            let len = strlen(v.as_ptr());
+            v.set_len(len);
+            let str = OsStr::from_bytes(std::mem::transmute(&v[..]));
             return Some(PathBuf::from(str));
         }
         if res != 0 {
@@ -77,3 +79,32 @@ pub fn get_resources_dir() -> Option<PathBuf> {
         Some(PathBuf::from((*str).as_str()))
     }
 }
+
+pub fn get_bundled_resource(subdir: &str, file_name: &str) -> Option<PathBuf> {
+    let path = Path::new(subdir).join(file_name);
+    let name = path.file_stem()?.to_str()?;
+    let ext = path.extension().and_then(|v| v.to_str()).unwrap_or("");
+    let dir = path.parent().and_then(|v| v.to_str()).unwrap_or("");
+    unsafe {
+        let nsbundle = class!(NSBundle);
+        let bundle: *mut Object = msg_send![nsbundle, mainBundle];
+        if bundle.is_null() {
+            return None;
+        }
+        let name = NSString::from_str(name);
+        let ext = NSString::from_str(ext);
+        let dir = match dir.is_empty() {
+            true => None,
+            false => Some(NSString::from_str(dir)),
+        };
+        let dir_ptr: *const NSString = match dir.as_ref() {
+            Some(v) => &**v,
+            None => std::ptr::null(),
+        };
+        let str: *const NSString = msg_send![bundle, pathForResource: &*name ofType: &*ext inDirectory: dir_ptr];
+        if str.is_null() {
+            return None;
+        }
+        Some(PathBuf::from((*str).as_str()))
+    }
+}
@@ -53,8 +53,9 @@ pub fn get_exe_path() -> Option<PathBuf> {
         if res == 0 {
             //FreeBSD without procfs.
             let len = strlen(buf.as_ptr() as _);
+            buf.set_len(len);
             //This is where we defer from process_path: we use std::os::unix::ffi::OsStrExt.
-            let str = OsStr::from_bytes(&buf[..len]);
+            let str = OsStr::from_bytes(&buf);
             let path = PathBuf::from(str);
             Some(path)
         } else {
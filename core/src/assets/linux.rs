@@ -37,8 +37,7 @@ pub fn get_exe_path() -> Option<PathBuf> {
     if !path.exists() {
         path = Path::new("/proc/curproc/file");
     }
-    let link = std::fs::read_link(path).ok()?;
-    link.parent().map(|v| v.into())
+    std::fs::read_link(path).ok()
 }
 
 pub fn get_resources_dir() -> Option<PathBuf> {
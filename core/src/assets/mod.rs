@@ -42,6 +42,9 @@ mod bsd;
 #[cfg(target_os = "windows")]
 mod windows;
 
+#[cfg(target_os = "android")]
+mod android;
+
 #[cfg(target_vendor = "apple")]
 use apple::{get_exe_path, get_resources_dir};
 
@@ -54,6 +57,9 @@ use bsd::{get_exe_path, get_resources_dir};
 #[cfg(target_os = "windows")]
 use windows::{get_exe_path, get_resources_dir};
 
+#[cfg(target_os = "android")]
+use android::{get_exe_path, get_resources_dir};
+
 /// Returns the path to an asset of the application.
 ///
 /// # Platform specific behavior
@@ -66,13 +72,264 @@ use windows::{get_exe_path, get_resources_dir};
 /// In the case a platform/packaging method isn't supported this function still returns a path based
 /// on executable location.
 ///
+/// On Android, assets are packaged inside the APK and aren't real files, as such this function
+/// always returns None. Use [read_app_bundled_asset] to read the content of an asset instead.
+///
 /// Returns None if there is a system issue, ex: the system didn't return a proper path to the current
 /// executing application. This should rarely occur.
 pub fn get_app_bundled_asset(file_name: &str) -> Option<PathBuf> {
-    let res = get_resources_dir().map(|v| v.join(file_name))
-        .or_else(|| get_exe_path().map(|v| v.join("Assets").join(file_name)));
-    if res.as_ref().map(|v| !v.exists()).unwrap_or(false) {
-        return None;
+    get_app_bundled_asset_in("", file_name)
+}
+
+/// Returns the path to an asset of the application located in a sub-directory of the assets
+/// directory.
+///
+/// # Platform specific behavior
+///
+/// On Apple platforms, the asset is first looked up using *pathForResource:ofType:inDirectory:* in
+/// *NSBundle* which also supports localized resources. On all other platforms, this function
+/// behaves like [get_app_bundled_asset] with the sub-directory joined to the assets directory.
+///
+/// # Arguments
+///
+/// * `subdir`: the sub-directory of the assets directory, ex: "textures/ui".
+/// * `file_name`: the name of the asset.
+///
+/// returns: Option<PathBuf>
+///
+/// Returns None if there is a system issue, ex: the system didn't return a proper path to the current
+/// executing application. This should rarely occur.
+pub fn get_app_bundled_asset_in(subdir: &str, file_name: &str) -> Option<PathBuf> {
+    #[cfg(target_vendor = "apple")]
+    //NSBundle caches the content of the bundle so the resource may have been deleted since.
+    if let Some(path) = apple::get_bundled_resource(subdir, file_name).filter(|v| v.exists()) {
+        return Some(path);
+    }
+    resolve_asset(subdir, file_name).filter(|v| v.exists())
+}
+
+/// Returns the path an asset of the application is expected at, whether the asset exists or not.
+///
+/// This function returns the path of the asset in the assets directory without checking that the
+/// asset exists; this is useful to report where an asset was expected.
+///
+/// # Platform specific behavior
+///
+/// On Apple platforms, [get_app_bundled_asset] first asks *NSBundle* which may return a different
+/// path, ex: a localized resource inside a *.lproj* directory. *NSBundle* only returns resources
+/// which exist, as such this function always returns the non-localized path in the resources
+/// directory of the bundle. On all other platforms, this is the path [get_app_bundled_asset]
+/// checks.
+///
+/// On Android, assets are packaged inside the APK and aren't real files, as such this function
+/// always returns None.
+///
+/// # Arguments
+///
+/// * `file_name`: the name of the asset.
+///
+/// returns: Option<PathBuf>
+///
+/// Returns None if there is a system issue, ex: the system didn't return a proper path to the current
+/// executing application. This should rarely occur.
+pub fn resolve_app_bundled_asset(file_name: &str) -> Option<PathBuf> {
+    resolve_asset("", file_name)
+}
+
+fn resolve_asset(subdir: &str, file_name: &str) -> Option<PathBuf> {
+    get_assets_dir().map(|v| v.join(subdir).join(file_name))
+}
+
+/// Lists all assets of the application contained in the given sub-directory.
+///
+/// The returned paths are relative to the assets directory, ex: "textures/logo.png", such that
+/// they can be passed to [get_app_bundled_asset] and [read_app_bundled_asset]. Pass an empty
+/// string to list the root of the assets directory.
+///
+/// # Platform specific behavior
+///
+/// On Android, this function lists the files in the *assets* folder of the APK using the NDK
+/// *AAssetManager*. Sub-directories are not listed. If no Android context was initialized,
+/// ex: a native binary started from a shell, this function returns None.
+/// When built with `panic = "abort"`, the process aborts instead because detecting the missing
+/// context relies on catching a panic; the panic message is printed to stderr in both cases.
+///
+/// # Arguments
+///
+/// * `subdir`: the sub-directory of the assets directory to list.
+///
+/// returns: Option<Vec<PathBuf>>
+///
+/// Returns None if the assets directory couldn't be located or if the sub-directory couldn't be
+/// read.
+pub fn list_app_bundled_assets(subdir: &str) -> Option<Vec<PathBuf>> {
+    #[cfg(target_os = "android")]
+    {
+        android::list_assets(subdir)
+    }
+    #[cfg(not(target_os = "android"))]
+    {
+        let entries = std::fs::read_dir(get_assets_dir()?.join(subdir)).ok()?;
+        entries
+            .map(|v| v.map(|v| std::path::Path::new(subdir).join(v.file_name())))
+            .collect::<std::io::Result<Vec<PathBuf>>>()
+            .ok()
+    }
+}
+
+/// Reads the content of an asset of the application.
+///
+/// The asset is located using [get_app_bundled_asset].
+///
+/// # Platform specific behavior
+///
+/// On Android, the asset is read from the *assets* folder of the APK using the NDK
+/// *AAssetManager*. If no Android context was initialized, ex: a native binary started from a
+/// shell, this function returns None.
+/// When built with `panic = "abort"`, the process aborts instead because detecting the missing
+/// context relies on catching a panic; the panic message is printed to stderr in both cases.
+///
+/// # Arguments
+///
+/// * `file_name`: the name of the asset to read.
+///
+/// returns: Option<Result<Vec<u8>, Error>>
+///
+/// Returns None if the asset couldn't be located.
+///
+/// # Errors
+///
+/// Returns an [Error](std::io::Error) if the asset couldn't be read.
+pub fn read_app_bundled_asset(file_name: &str) -> Option<std::io::Result<Vec<u8>>> {
+    #[cfg(target_os = "android")]
+    {
+        android::read_asset(file_name)
+    }
+    #[cfg(not(target_os = "android"))]
+    {
+        get_app_bundled_asset(file_name).map(std::fs::read)
+    }
+}
+
+/// Returns the path to the executable file of the current application.
+///
+/// # Platform specific behavior
+///
+/// - On macOS and iOS, this function calls *_NSGetExecutablePath*.
+///
+/// - On Windows, this function calls *GetModuleFileNameW*.
+///
+/// - On Linux, this function reads the */proc/self/exe* symbolic link.
+///
+/// - On BSD systems, this function calls *sysctl* with *KERN_PROC_PATHNAME* and falls back to
+///   */proc/curproc/file*.
+///
+/// - On Android, this function always returns None because applications are not started from an
+///   executable file.
+///
+/// returns: Option<PathBuf>
+///
+/// Returns None if the system didn't return a proper path to the current executing application.
+pub fn get_executable_path() -> Option<PathBuf> {
+    get_exe_path()
+}
+
+/// Returns the path to the directory containing the executable file of the current application.
+///
+/// See [get_executable_path] for details.
+///
+/// returns: Option<PathBuf>
+pub fn get_executable_dir() -> Option<PathBuf> {
+    get_exe_path().and_then(|v| v.parent().map(PathBuf::from))
+}
+
+fn get_assets_dir() -> Option<PathBuf> {
+    get_resources_dir().or_else(|| get_executable_dir().map(|v| v.join("Assets")))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::assets::{
+        get_app_bundled_asset, get_app_bundled_asset_in, get_assets_dir, get_executable_dir,
+        get_executable_path, list_app_bundled_assets, read_app_bundled_asset,
+        resolve_app_bundled_asset,
+    };
+    use std::panic::AssertUnwindSafe;
+    use std::path::Path;
+    use std::sync::Mutex;
+
+    //Tests share the assets directory next to the test executable.
+    static LOCK: Mutex<()> = Mutex::new(());
+
+    fn with_assets<R, F: FnOnce(&Path) -> R>(name: &str, f: F) -> R {
+        let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let assets = get_assets_dir().unwrap();
+        let existed = assets.exists();
+        let dir = assets.join(name);
+        std::fs::create_dir_all(&dir).unwrap();
+        let res = std::panic::catch_unwind(AssertUnwindSafe(|| f(&dir)));
+        std::fs::remove_dir_all(&dir).unwrap();
+        if !existed {
+            std::fs::remove_dir(&assets).unwrap();
+        }
+        match res {
+            Ok(v) => v,
+            Err(e) => std::panic::resume_unwind(e),
+        }
+    }
+
+    #[test]
+    fn list_assets() {
+        with_assets("list_assets", |dir| {
+            std::fs::write(dir.join("a.txt"), b"a").unwrap();
+            std::fs::write(dir.join("b.txt"), b"b").unwrap();
+            let mut assets = list_app_bundled_assets("list_assets").unwrap();
+            assets.sort();
+            assert_eq!(assets, vec![Path::new("list_assets/a.txt"), Path::new("list_assets/b.txt")]);
+            let data = read_app_bundled_asset(assets[0].to_str().unwrap()).unwrap().unwrap();
+            assert_eq!(data, b"a");
+        });
+        assert!(list_app_bundled_assets("list_assets").is_none());
+    }
+
+    #[test]
+    fn read_asset() {
+        with_assets("read_asset", |dir| {
+            std::fs::write(dir.join("file.bin"), b"\x00\x01test").unwrap();
+            let data = read_app_bundled_asset("read_asset/file.bin").unwrap().unwrap();
+            assert_eq!(data, b"\x00\x01test");
+        });
+        assert!(read_app_bundled_asset("read_asset/file.bin").is_none());
+    }
+
+    #[test]
+    fn nested_asset() {
+        with_assets("nested_asset", |dir| {
+            std::fs::create_dir_all(dir.join("textures").join("ui")).unwrap();
+            std::fs::write(dir.join("textures").join("ui").join("logo.png"), b"png").unwrap();
+            let path = get_app_bundled_asset_in("nested_asset/textures/ui", "logo.png");
+            let path1 = get_app_bundled_asset("nested_asset/textures/ui/logo.png");
+            let missing = get_app_bundled_asset_in("nested_asset/textures", "logo.png");
+            assert_eq!(path, Some(dir.join("textures").join("ui").join("logo.png")));
+            assert_eq!(path1, path);
+            assert_eq!(missing, None);
+        });
+    }
+
+    #[test]
+    fn executable_path() {
+        let path = get_executable_path().unwrap();
+        let exe = std::env::current_exe().unwrap();
+        assert!(path.is_file());
+        assert_eq!(path.file_name(), exe.file_name());
+        assert_eq!(get_executable_dir().as_deref(), path.parent());
+    }
+
+    #[test]
+    fn resolve_missing_asset() {
+        let path = resolve_app_bundled_asset("missing_asset.txt").unwrap();
+        assert_eq!(path, get_assets_dir().unwrap().join("missing_asset.txt"));
+        assert!(!path.exists());
+        assert!(get_app_bundled_asset("missing_asset.txt").is_none());
     }
-    res
 }
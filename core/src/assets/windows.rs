@@ -54,13 +54,11 @@ pub fn get_exe_path() -> Option<PathBuf> {
                     let res = GetModuleFileNameW(0, v.as_mut_ptr(), len as u32);
                     if res == 0 {
                         return None; //System error.
-                    } else if res == len as u32 {
-                        let err = GetLastError();
-                        if err != ERROR_INSUFFICIENT_BUFFER {
-                            break;
-                        }
-                    } else {
-                        break;
+                    } else if res != len as u32 || GetLastError() != ERROR_INSUFFICIENT_BUFFER {
+                        //The heap buffer was large enough.
+                        v.set_len(res as usize);
+                        let str1 = OsString::from_wide(&v);
+                        return Some(str1.into());
                     }
                     //If this reaches, well it's still not looking good, and we need more re-allocations.
                     len *= 2;
@@ -55,8 +55,18 @@ impl From<std::io::Error> for Error {
 /// These APIs will fail as last resort. If they fail it usually means the system has a problem.
 /// The system may also include specific configuration to break applications on purpose,
 /// in which case these APIs will also fail.
+///
+/// # Application directory
+///
+/// The directories returned by these APIs are named after the application, which is written
+/// {APP_DIR} in the documentation of each function. For an application created with
+/// [new](App::new), {APP_DIR} is the name of the application. For an application created with
+/// [with_identifier](App::with_identifier), {APP_DIR} is {ORGANIZATION}/{APP} on most platforms
+/// and {BUNDLE_ID} on Apple platforms.
 pub struct App<'a> {
     name: &'a str,
+    organization: Option<&'a str>,
+    bundle_id: Option<&'a str>,
     data: OnceCell<PathBuf>,
     cache: OnceCell<PathBuf>,
     docs: OnceCell<PathBuf>,
@@ -75,6 +85,8 @@ impl<'a> App<'a> {
     pub fn new(name: &'a str) -> App<'a> {
         App {
             name,
+            organization: None,
+            bundle_id: None,
             data: OnceCell::new(),
             cache: OnceCell::new(),
             docs: OnceCell::new(),
@@ -83,10 +95,47 @@ impl<'a> App<'a> {
         }
     }
 
+    /// Creates a new application identified by an organization and a bundle identifier.
+    ///
+    /// # Platform specific behavior
+    ///
+    /// On Apple platforms, the application's directories are named after the bundle identifier,
+    /// ex: ~/Library/Application Support/{BUNDLE_ID}, which is how macOS and iOS key application
+    /// containers. On all other platforms, the application's directories are placed in an
+    /// organization directory, ex: ~/.local/share/{ORGANIZATION}/{APP}.
+    ///
+    /// # Arguments
+    ///
+    /// * `name`: the name of the application.
+    /// * `organization`: the name of the organization publishing the application.
+    /// * `bundle_id`: the bundle identifier of the application, ex: com.example.app.
+    ///
+    /// returns: App
+    pub fn with_identifier(name: &'a str, organization: &'a str, bundle_id: &'a str) -> App<'a> {
+        App {
+            organization: Some(organization),
+            bundle_id: Some(bundle_id),
+            ..App::new(name)
+        }
+    }
+
+    fn relative_path(&self) -> PathBuf {
+        #[cfg(target_vendor = "apple")]
+        if let Some(bundle_id) = self.bundle_id {
+            return PathBuf::from(bundle_id);
+        }
+        match self.organization {
+            Some(organization) => Path::new(organization).join(self.name),
+            None => PathBuf::from(self.name),
+        }
+    }
+
     /// Returns the path to this application's files.
     ///
     /// Use this directory to store any information not intended to be user accessible.
     ///
+    /// This function uses [get_app_data](system::get_app_data)/{APP_DIR}.
+    ///
     /// # Errors
     ///
     /// Returns a [MissingDataDir](self::Error::MissingDataDir) if this system doesn't have any application
@@ -98,7 +147,7 @@ impl<'a> App<'a> {
             .get_or_try_init(|| {
                 let data = system::get_app_data()
                     .ok_or(Error::MissingDataDir)?
-                    .join(self.name);
+                    .join(self.relative_path());
                 if !data.is_dir() {
                     std::fs::create_dir_all(&data)?;
                 }
@@ -107,11 +156,21 @@ impl<'a> App<'a> {
             .map(|v| v.as_ref())
     }
 
+    /// Returns the path to this application's files shared by all users of this machine.
+    ///
+    /// This function uses [get_shared_data](system::get_shared_data)/{APP_DIR}. Unlike other
+    /// directories, this directory is not created as writing to it may require elevated privileges.
+    ///
+    /// Returns None if the system doesn't have a shared data directory.
+    pub fn get_shared_data(&self) -> Option<PathBuf> {
+        system::get_shared_data().map(|v| v.join(self.relative_path()))
+    }
+
     /// Returns the path to this application's cache.
     ///
     /// Use this directory to store cached files such as downloads, intermediate files, etc.
     ///
-    /// This function first tries to use [get_app_cache](system::get_app_cache)/{APP} and
+    /// This function first tries to use [get_app_cache](system::get_app_cache)/{APP_DIR} and
     /// falls back [get_data](App::get_data)/Cache.
     ///
     /// # Errors
@@ -120,18 +179,74 @@ impl<'a> App<'a> {
     pub fn get_cache(&self) -> Result<&Path, Error> {
         self.cache
             .get_or_try_init(|| {
-                let cache = match system::get_app_cache() {
-                    None => self.get_data()?.join("Cache"),
-                    Some(cache) => cache.join(self.name),
-                };
+                let cache = self.cache_path().ok_or(Error::MissingDataDir)?;
                 if !cache.is_dir() {
-                    std::fs::create_dir(&cache)?;
+                    std::fs::create_dir_all(&cache)?;
                 }
                 Ok(cache)
             })
             .map(|v| v.as_ref())
     }
 
+    fn cache_path(&self) -> Option<PathBuf> {
+        match system::get_app_cache() {
+            None => system::get_app_data().map(|v| v.join(self.relative_path()).join("Cache")),
+            Some(cache) => Some(cache.join(self.relative_path())),
+        }
+    }
+
+    /// Deletes the content of this application's cache.
+    ///
+    /// The cache directory itself is kept. This function uses the same location as
+    /// [get_cache](App::get_cache) but does not create the cache directory if it does not exist.
+    ///
+    /// returns: Result<u64, Error>
+    ///
+    /// Returns the number of bytes freed; if the cache directory does not exist this function
+    /// returns 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [Error](std::io::Error) if some file or directory couldn't be deleted.
+    pub fn clear_cache(&self) -> std::io::Result<u64> {
+        match self.cache.get().cloned().or_else(|| self.cache_path()) {
+            Some(cache) => clear_dir(&cache),
+            None => Ok(0),
+        }
+    }
+
+    /// Moves the directories of an application previously named `old_name` to this application.
+    ///
+    /// For each of the system data, cache, config and logs directories, the directory of the old
+    /// application is moved to the directory of this application, unless the latter already exists.
+    /// Directories which fall back inside the data directory (ex: [get_data](App::get_data)/Cache)
+    /// are moved along with the data directory. Per-application documents directories are never
+    /// moved as they are not named after the application.
+    ///
+    /// This function must be called before any directory of this application is requested,
+    /// otherwise the new directories already exist and nothing is migrated.
+    ///
+    /// # Arguments
+    ///
+    /// * `old_name`: the previous name of the application.
+    ///
+    /// returns: Result<bool, Error>
+    ///
+    /// Returns true if at least one directory was migrated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [Error](std::io::Error) if some directory couldn't be moved.
+    pub fn migrate_from(&self, old_name: &str) -> std::io::Result<bool> {
+        let roots = [
+            system::get_app_data(),
+            system::get_app_cache(),
+            system::get_app_config(),
+            system::get_app_logs(),
+        ];
+        migrate_dirs(roots.iter().flatten(), Path::new(old_name), &self.relative_path())
+    }
+
     /// Returns the path to this application's public documents.
     ///
     /// Use this directory to store any content the user should see and alter.
@@ -164,7 +279,7 @@ impl<'a> App<'a> {
     ///
     /// Use this directory to store all logs. The user can view and alter this directory.
     ///
-    /// This function first tries to use [get_app_logs](system::get_app_logs)/{APP} and
+    /// This function first tries to use [get_app_logs](system::get_app_logs)/{APP_DIR} and
     /// falls back [get_documents](App::get_documents)/Logs.
     ///
     /// # Errors
@@ -177,10 +292,10 @@ impl<'a> App<'a> {
             .get_or_try_init(|| {
                 let logs = match system::get_app_logs() {
                     None => self.get_documents()?.join("Logs"),
-                    Some(logs) => logs.join(self.name),
+                    Some(logs) => logs.join(self.relative_path()),
                 };
                 if !logs.is_dir() {
-                    std::fs::create_dir(&logs)?;
+                    std::fs::create_dir_all(&logs)?;
                 }
                 Ok(logs)
             })
@@ -192,7 +307,7 @@ impl<'a> App<'a> {
     /// Use this directory to store all configs for the current user.
     /// This directory is not intended for direct user access.
     ///
-    /// This function first tries to use [get_app_config](system::get_app_config)/{APP} and
+    /// This function first tries to use [get_app_config](system::get_app_config)/{APP_DIR} and
     /// falls back [get_data](App::get_data)/Config.
     ///
     /// # Errors
@@ -203,10 +318,10 @@ impl<'a> App<'a> {
             .get_or_try_init(|| {
                 let config = match system::get_app_config() {
                     None => self.get_data()?.join("Config"),
-                    Some(config) => config.join(self.name),
+                    Some(config) => config.join(self.relative_path()),
                 };
                 if !config.is_dir() {
-                    std::fs::create_dir(&config)?;
+                    std::fs::create_dir_all(&config)?;
                 }
                 Ok(config)
             })
@@ -214,10 +329,35 @@ impl<'a> App<'a> {
     }
 }
 
+fn clear_dir(path: &Path) -> std::io::Result<u64> {
+    match path.is_dir() {
+        true => crate::fs::remove_dir_all_contents(path),
+        false => Ok(0),
+    }
+}
+
+fn migrate_dirs<'b, I: Iterator<Item = &'b PathBuf>>(roots: I, old: &Path, new: &Path) -> std::io::Result<bool> {
+    let mut migrated = false;
+    for root in roots {
+        let old = root.join(old);
+        let new = root.join(new);
+        if old.is_dir() && !new.exists() {
+            if let Some(parent) = new.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::rename(old, new)?;
+            migrated = true;
+        }
+    }
+    Ok(migrated)
+}
+
 impl<'a> Clone for App<'a> {
     fn clone(&self) -> Self {
         App {
             name: self.name,
+            organization: self.organization,
+            bundle_id: self.bundle_id,
             data: self.data.clone(),
             cache: self.cache.clone(),
             docs: self.docs.clone(),
@@ -229,7 +369,8 @@ impl<'a> Clone for App<'a> {
 
 #[cfg(test)]
 mod tests {
-    use crate::dirs::App;
+    use crate::dirs::{migrate_dirs, system, App};
+    use std::path::Path;
 
     fn assert_sync_send<T: Sync + Send>(x: T) -> T {
         x
@@ -240,4 +381,74 @@ mod tests {
         let obj = App::new("test");
         let _ = assert_sync_send(obj);
     }
+
+    #[test]
+    fn test_clear_cache() {
+        let app = App::new("test");
+        let expected = match system::get_app_cache() {
+            Some(cache) => cache.join("test"),
+            None => system::get_app_data().unwrap().join("test").join("Cache"),
+        };
+        assert_eq!(app.cache_path(), Some(expected));
+        let cache = std::env::temp_dir().join("bp3d-os-test-clear-cache");
+        let _ = std::fs::remove_dir_all(&cache);
+        std::fs::create_dir_all(cache.join("dir")).unwrap();
+        std::fs::write(cache.join("file.bin"), [0; 128]).unwrap();
+        std::fs::write(cache.join("dir").join("file.bin"), [0; 64]).unwrap();
+        //Point the app to the temporary cache such that the real cache is left untouched.
+        app.cache.set(cache.clone()).unwrap();
+        let size = app.clear_cache().unwrap();
+        assert_eq!(size, 192);
+        assert!(cache.is_dir());
+        assert_eq!(std::fs::read_dir(&cache).unwrap().count(), 0);
+        std::fs::remove_dir(&cache).unwrap();
+        assert_eq!(app.clear_cache().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_identifier() {
+        let app = App::new("test");
+        assert_eq!(app.relative_path(), Path::new("test"));
+        let app = App::with_identifier("test", "BlockProject 3D", "com.bp3d.test");
+        #[cfg(target_vendor = "apple")]
+        assert_eq!(app.relative_path(), Path::new("com.bp3d.test"));
+        #[cfg(not(target_vendor = "apple"))]
+        assert_eq!(app.relative_path(), Path::new("BlockProject 3D").join("test"));
+    }
+
+    #[test]
+    fn test_migrate() {
+        let root = std::env::temp_dir().join("bp3d-os-test-migrate");
+        let _ = std::fs::remove_dir_all(&root);
+        let roots = [root.join("data"), root.join("cache"), root.join("config")];
+        std::fs::create_dir_all(roots[0].join("old")).unwrap();
+        std::fs::write(roots[0].join("old").join("file.txt"), b"data").unwrap();
+        std::fs::create_dir_all(roots[1].join("old")).unwrap();
+        std::fs::create_dir_all(roots[1].join("org").join("new")).unwrap();
+        let new = Path::new("org").join("new");
+        assert!(migrate_dirs(roots.iter(), Path::new("old"), &new).unwrap());
+        assert!(!roots[0].join("old").exists());
+        assert_eq!(std::fs::read(roots[0].join(&new).join("file.txt")).unwrap(), b"data");
+        //The new directory already exists in the cache root so the old one must be kept.
+        assert!(roots[1].join("old").is_dir());
+        assert!(!roots[2].exists());
+        assert!(!migrate_dirs(roots.iter(), Path::new("old"), &new).unwrap());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_shared_data() {
+        let app = App::new("test");
+        assert_eq!(app.get_shared_data(), system::get_shared_data().map(|v| v.join("test")));
+        #[cfg(target_os = "macos")]
+        assert_eq!(system::get_shared_data(), Some(Path::new("/Library/Application Support").into()));
+        #[cfg(target_os = "ios")]
+        assert_eq!(system::get_shared_data(), None);
+        #[cfg(windows)]
+        assert!(system::get_shared_data().unwrap().is_absolute());
+        #[cfg(all(unix, not(any(target_vendor = "apple", target_os = "android"))))]
+        if std::env::var_os("XDG_DATA_DIRS").is_none() {
+            assert_eq!(system::get_shared_data(), Some(Path::new("/usr/local/share").into()));
+        }
+    }
 }
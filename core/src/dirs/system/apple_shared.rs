@@ -79,3 +79,25 @@ pub fn get_macos_dir_fail_if_sandbox(directory: c_ulong) -> Option<PathBuf> {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        get_macos_dir, NS_APPLICATION_SUPPORT_DIRECTORY, NS_CACHES_DIRECTORY,
+        NS_DOCUMENT_DIRECTORY, NS_LIBRARY_DIRECTORY,
+    };
+
+    #[test]
+    fn app_dirs() {
+        //These are the directories an iOS application must use to store its files.
+        let dirs = [
+            NS_DOCUMENT_DIRECTORY,
+            NS_CACHES_DIRECTORY,
+            NS_APPLICATION_SUPPORT_DIRECTORY,
+            NS_LIBRARY_DIRECTORY,
+        ];
+        for dir in dirs.iter() {
+            assert!(get_macos_dir(*dir).is_some());
+        }
+    }
+}
@@ -62,3 +62,11 @@ pub fn get_user_documents() -> Option<PathBuf> {
 pub fn get_user_downloads() -> Option<PathBuf> {
     None
 }
+
+//On iOS there exists no shared directories as all applications are sandboxed
+pub fn get_shared_data() -> Option<PathBuf> {
+    None
+}
+pub fn get_shared_config() -> Option<PathBuf> {
+    None
+}
@@ -73,3 +73,11 @@ pub fn get_user_documents() -> Option<PathBuf> {
 pub fn get_user_downloads() -> Option<PathBuf> {
     get_macos_dir_fail_if_sandbox(NS_DOWNLOADS_DIRECTORY)
 }
+
+pub fn get_shared_data() -> Option<PathBuf> {
+    Some(PathBuf::from("/Library/Application Support"))
+}
+
+pub fn get_shared_config() -> Option<PathBuf> {
+    Some(PathBuf::from("/Library/Preferences"))
+}
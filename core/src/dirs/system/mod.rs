@@ -29,6 +29,9 @@
 //! Low-level access to standard system directories.
 //!
 //! Unsupported directories are returned as None.
+//!
+//! On iOS, all applications are sandboxed: {SANDBOX} refers to the application's own container
+//! which is the only writable location available to the application.
 
 use std::path::PathBuf;
 
@@ -66,7 +69,7 @@ use windows as _impl;
 /// |----------------------|-----------------------|-----------------------------------------------|
 /// | macOS                | NS_CACHES_DIRECTORY   | ~/Library/Caches                              |
 /// | macOS (with sandbox) | NS_CACHES_DIRECTORY   | ~/Library/Containers/{ID}/Data/Library/Caches |
-/// | iOS                  | NS_CACHES_DIRECTORY   | Not applicable                                |
+/// | iOS                  | NS_CACHES_DIRECTORY   | {SANDBOX}/Library/Caches                      |
 /// | Linux                | XDG_CACHE_HOME        | ~/.cache                                      |
 /// | Windows              | FOLDERID_LocalAppData | %APPDATA%/Local                               |
 pub fn get_app_cache() -> Option<PathBuf> {
@@ -81,7 +84,7 @@ pub fn get_app_cache() -> Option<PathBuf> {
 /// |----------------------|----------------------------------|----------------------------------------------------|
 /// | macOS                | NS_LIBRARY_DIRECTORY/Preferences | ~/Library/Preferences                              |
 /// | macOS (with sandbox) | NS_LIBRARY_DIRECTORY/Preferences | ~/Library/Containers/{ID}/Data/Library/Preferences |
-/// | iOS                  | NS_LIBRARY_DIRECTORY/Preferences | Not applicable                                     |
+/// | iOS                  | NS_LIBRARY_DIRECTORY/Preferences | {SANDBOX}/Library/Preferences                      |
 /// | Linux                | XDG_CONFIG_HOME                  | ~/.config                                          |
 /// | Windows              | None                             | None                                               |
 pub fn get_app_config() -> Option<PathBuf> {
@@ -96,7 +99,7 @@ pub fn get_app_config() -> Option<PathBuf> {
 /// |----------------------|----------------------------------|------------------------------------------------------------|
 /// | macOS                | NS_APPLICATION_SUPPORT_DIRECTORY | ~/Library/Application Support                              |
 /// | macOS (with sandbox) | NS_APPLICATION_SUPPORT_DIRECTORY | ~/Library/Containers/{ID}/Data/Library/Application Support |
-/// | iOS                  | NS_APPLICATION_SUPPORT_DIRECTORY | Not applicable                                             |
+/// | iOS                  | NS_APPLICATION_SUPPORT_DIRECTORY | {SANDBOX}/Library/Application Support                      |
 /// | Linux                | XDG_DATA_HOME                    | ~/.local/share                                             |
 /// | Windows              | FOLDERID_RoamingAppData          | %APPDATA%/Roaming                                          |
 pub fn get_app_data() -> Option<PathBuf> {
@@ -128,7 +131,7 @@ pub fn get_app_logs() -> Option<PathBuf> {
 /// |----------------------|-----------------------|------------------------------------------|
 /// | macOS                | None                  | None                                     |
 /// | macOS (with sandbox) | NS_DOCUMENT_DIRECTORY | ~/Library/Containers/{ID}/Data/Documents |
-/// | iOS                  | NS_DOCUMENT_DIRECTORY | Not applicable                           |
+/// | iOS                  | NS_DOCUMENT_DIRECTORY | {SANDBOX}/Documents                      |
 /// | Linux                | None                  | None                                     |
 /// | Windows              | None                  | None                                     |
 pub fn get_app_documents() -> Option<PathBuf> {
@@ -179,3 +182,37 @@ pub fn get_user_documents() -> Option<PathBuf> {
 pub fn get_user_downloads() -> Option<PathBuf> {
     _impl::get_user_downloads()
 }
+
+/// Returns the system-wide data directory shared by all users of this machine.
+///
+/// **NOTE: Writing to this directory may require elevated privileges.**
+///
+/// # Platform specific behavior
+///
+/// | System               | Directory Name           | Usual path                    |
+/// |----------------------|--------------------------|-------------------------------|
+/// | macOS                | None                     | /Library/Application Support  |
+/// | macOS (with sandbox) | None                     | /Library/Application Support  |
+/// | iOS                  | None                     | None                          |
+/// | Linux                | XDG_DATA_DIRS            | /usr/local/share              |
+/// | Windows              | FOLDERID_ProgramData     | C:\ProgramData                |
+pub fn get_shared_data() -> Option<PathBuf> {
+    _impl::get_shared_data()
+}
+
+/// Returns the system-wide config directory shared by all users of this machine.
+///
+/// **NOTE: Writing to this directory may require elevated privileges.**
+///
+/// # Platform specific behavior
+///
+/// | System               | Directory Name           | Usual path                    |
+/// |----------------------|--------------------------|-------------------------------|
+/// | macOS                | None                     | /Library/Preferences          |
+/// | macOS (with sandbox) | None                     | /Library/Preferences          |
+/// | iOS                  | None                     | None                          |
+/// | Linux                | XDG_CONFIG_DIRS          | /etc/xdg                      |
+/// | Windows              | FOLDERID_ProgramData     | C:\ProgramData                |
+pub fn get_shared_config() -> Option<PathBuf> {
+    _impl::get_shared_config()
+}
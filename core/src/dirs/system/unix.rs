@@ -26,24 +26,65 @@
 // NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use std::ffi::{CStr, OsStr, OsString};
+use std::os::unix::ffi::OsStrExt;
 use std::path::PathBuf;
 
+fn get_home() -> Option<PathBuf> {
+    resolve_home(std::env::var_os("HOME").map(|v| v.into()), get_passwd_home)
+}
+
+fn resolve_home<F: FnOnce() -> Option<PathBuf>>(home: Option<PathBuf>, passwd: F) -> Option<PathBuf> {
+    //HOME is usually unset in minimal environments such as systemd services, so fallback to the
+    //passwd database like login shells do.
+    home.filter(|v| !v.as_os_str().is_empty()).or_else(passwd)
+}
+
+//Upper bound of the buffer used to read the passwd entry, to avoid growing it forever should
+// getpwuid_r keep returning ERANGE.
+const MAX_PASSWD_BUF_LEN: usize = 1 << 20;
+
+fn get_passwd_home() -> Option<PathBuf> {
+    let mut buf: Vec<libc::c_char> = vec![0; 4096];
+    loop {
+        unsafe {
+            let mut pwd: libc::passwd = std::mem::zeroed();
+            let mut res: *mut libc::passwd = std::ptr::null_mut();
+            let err = libc::getpwuid_r(libc::getuid(), &mut pwd, buf.as_mut_ptr(), buf.len(), &mut res);
+            if err == libc::ERANGE && buf.len() < MAX_PASSWD_BUF_LEN {
+                //The buffer is not large enough to hold the passwd entry.
+                let len = buf.len() * 2;
+                buf.resize(len, 0);
+                continue;
+            }
+            if err != 0 || res.is_null() || pwd.pw_dir.is_null() {
+                return None;
+            }
+            let dir = CStr::from_ptr(pwd.pw_dir).to_bytes();
+            if dir.is_empty() {
+                return None;
+            }
+            return Some(PathBuf::from(OsStr::from_bytes(dir)));
+        }
+    }
+}
+
 pub fn get_app_cache() -> Option<PathBuf> {
     std::env::var_os("XDG_CACHE_HOME")
         .map(|v| v.into())
-        .or_else(|| std::env::var_os("HOME").map(|v| PathBuf::from(v).join(".cache")))
+        .or_else(|| get_home().map(|v| v.join(".cache")))
 }
 
 pub fn get_app_config() -> Option<PathBuf> {
     std::env::var_os("XDG_CONFIG_HOME")
         .map(|v| v.into())
-        .or_else(|| std::env::var_os("HOME").map(|v| PathBuf::from(v).join(".config")))
+        .or_else(|| get_home().map(|v| v.join(".config")))
 }
 
 pub fn get_app_data() -> Option<PathBuf> {
     std::env::var_os("XDG_DATA_HOME")
         .map(|v| v.into())
-        .or_else(|| std::env::var_os("HOME").map(|v| PathBuf::from(v).join(".local/share")))
+        .or_else(|| get_home().map(|v| v.join(".local/share")))
 }
 
 pub fn get_app_logs() -> Option<PathBuf> {
@@ -55,17 +96,53 @@ pub fn get_app_documents() -> Option<PathBuf> {
 }
 
 pub fn get_user_home() -> Option<PathBuf> {
-    std::env::var_os("HOME").map(|v| v.into())
+    get_home()
 }
 
 pub fn get_user_documents() -> Option<PathBuf> {
     std::env::var_os("XDG_DOCUMENTS_DIR")
         .map(|v| v.into())
-        .or_else(|| std::env::var_os("HOME").map(|v| PathBuf::from(v).join("Documents")))
+        .or_else(|| get_home().map(|v| v.join("Documents")))
 }
 
 pub fn get_user_downloads() -> Option<PathBuf> {
     std::env::var_os("XDG_DOWNLOAD_DIR")
         .map(|v| v.into())
-        .or_else(|| std::env::var_os("HOME").map(|v| PathBuf::from(v).join("Downloads")))
+        .or_else(|| get_home().map(|v| v.join("Downloads")))
+}
+
+fn get_first_dir(dirs: Option<OsString>, default: &str) -> PathBuf {
+    dirs.and_then(|v| std::env::split_paths(&v).find(|v| !v.as_os_str().is_empty()))
+        .unwrap_or_else(|| PathBuf::from(default))
+}
+
+pub fn get_shared_data() -> Option<PathBuf> {
+    Some(get_first_dir(std::env::var_os("XDG_DATA_DIRS"), "/usr/local/share"))
+}
+
+pub fn get_shared_config() -> Option<PathBuf> {
+    Some(get_first_dir(std::env::var_os("XDG_CONFIG_DIRS"), "/etc/xdg"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_first_dir, resolve_home};
+    use std::path::PathBuf;
+
+    #[test]
+    fn home_fallback() {
+        let fake = || Some(PathBuf::from("/home/fake"));
+        assert_eq!(resolve_home(None, fake), Some(PathBuf::from("/home/fake")));
+        assert_eq!(resolve_home(Some(PathBuf::new()), fake), Some(PathBuf::from("/home/fake")));
+        assert_eq!(resolve_home(Some(PathBuf::from("/home/user")), fake), Some(PathBuf::from("/home/user")));
+        assert_eq!(resolve_home(None, || None), None);
+    }
+
+    #[test]
+    fn shared_dirs() {
+        assert_eq!(get_first_dir(None, "/usr/local/share"), PathBuf::from("/usr/local/share"));
+        assert_eq!(get_first_dir(Some("".into()), "/usr/local/share"), PathBuf::from("/usr/local/share"));
+        assert_eq!(get_first_dir(Some("/usr/share:/opt/share".into()), "/usr/local/share"), PathBuf::from("/usr/share"));
+        assert_eq!(get_first_dir(Some(":/opt/share".into()), "/usr/local/share"), PathBuf::from("/opt/share"));
+    }
 }
@@ -35,7 +35,7 @@ use windows_sys::Win32::Foundation::S_OK;
 use windows_sys::Win32::System::Com::CoTaskMemFree;
 use windows_sys::Win32::UI::Shell::{
     FOLDERID_Documents, FOLDERID_Downloads, FOLDERID_LocalAppData, FOLDERID_Profile,
-    FOLDERID_RoamingAppData, SHGetKnownFolderPath,
+    FOLDERID_ProgramData, FOLDERID_RoamingAppData, SHGetKnownFolderPath,
 };
 
 fn get_windows_path(folder: GUID) -> Option<PathBuf> {
@@ -88,3 +88,12 @@ pub fn get_user_documents() -> Option<PathBuf> {
 pub fn get_user_downloads() -> Option<PathBuf> {
     get_windows_path(FOLDERID_Downloads)
 }
+
+pub fn get_shared_data() -> Option<PathBuf> {
+    get_windows_path(FOLDERID_ProgramData)
+}
+
+pub fn get_shared_config() -> Option<PathBuf> {
+    //ProgramData is used for both machine-wide data and configurations under windows.
+    get_windows_path(FOLDERID_ProgramData)
+}
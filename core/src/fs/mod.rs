@@ -26,8 +26,8 @@
 // NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-//! This module provides cross-platform functions to hide, unhide files, manage file extensions and
-//! get the most compatible absolute path of a file.
+//! This module provides cross-platform functions to hide, unhide, compare, touch and delete files, manage
+//! file extensions and get the most compatible absolute path of a file.
 
 #[cfg(unix)]
 mod unix;
@@ -36,16 +36,295 @@ mod unix;
 mod windows;
 
 #[cfg(unix)]
-pub use unix::{get_absolute_path, hide, unhide, is_hidden};
+pub use unix::{get_absolute_path, hide, unhide, is_hidden, same_file};
 
 #[cfg(windows)]
-pub use windows::{get_absolute_path, hide, unhide, is_hidden};
+pub use windows::{get_absolute_path, hide, unhide, is_hidden, same_file};
+
+#[cfg(unix)]
+use unix::remove_file;
+
+#[cfg(windows)]
+use windows::remove_file;
+
+/// Returns the canonical form of a path with all symbolic links resolved, without the `\\?\`
+/// prefix on Windows when the path is valid without it.
+///
+/// Unlike [get_absolute_path], which may change how it converts paths in the future, this
+/// function always has [canonicalize](std::fs::canonicalize) semantics. On unix systems, this is
+/// the same as [canonicalize](std::fs::canonicalize).
+///
+/// # Arguments
+///
+/// * `path`: the path to canonicalize.
+///
+/// returns: Result<PathBuf, Error>
+///
+/// # Errors
+///
+/// Returns an [Error](std::io::Error) if the path couldn't be canonicalized.
+pub fn canonicalize_no_unc<T: AsRef<std::path::Path>>(path: T) -> std::io::Result<std::path::PathBuf> {
+    #[cfg(windows)]
+    {
+        dunce::canonicalize(path)
+    }
+    #[cfg(unix)]
+    {
+        std::fs::canonicalize(path)
+    }
+}
+
+/// Deletes all files and directories contained in a directory without deleting the directory
+/// itself.
+///
+/// Symbolic links contained in the directory are deleted but never followed. If `path` itself is
+/// a symbolic link to a directory, it is followed: the content of the target directory is deleted
+/// and the link is kept. On Windows, read-only files are deleted as well.
+///
+/// # Arguments
+///
+/// * `path`: the directory to empty.
+///
+/// returns: Result<u64, Error>
+///
+/// Returns the number of bytes freed.
+///
+/// # Errors
+///
+/// Returns an [Error](std::io::Error) if some file or directory couldn't be deleted.
+pub fn remove_dir_all_contents<T: AsRef<std::path::Path>>(path: T) -> std::io::Result<u64> {
+    let mut size = 0;
+    for entry in std::fs::read_dir(path)? {
+        let entry = entry?;
+        let metadata = std::fs::symlink_metadata(entry.path())?;
+        size += remove_entry(&entry.path(), &metadata)?;
+    }
+    Ok(size)
+}
+
+/// Deletes a file or a directory and all its content.
+///
+/// Symbolic links are deleted but never followed. On Windows, read-only files are deleted as well.
+///
+/// # Arguments
+///
+/// * `path`: the file or directory to delete.
+///
+/// returns: Result<u64, Error>
+///
+/// Returns the number of bytes freed.
+///
+/// # Errors
+///
+/// Returns an [Error](std::io::Error) if some file or directory couldn't be deleted.
+pub fn remove<T: AsRef<std::path::Path>>(path: T) -> std::io::Result<u64> {
+    let path = path.as_ref();
+    let metadata = std::fs::symlink_metadata(path)?;
+    remove_entry(path, &metadata)
+}
+
+fn remove_entry(path: &std::path::Path, metadata: &std::fs::Metadata) -> std::io::Result<u64> {
+    if metadata.is_dir() {
+        let size = remove_dir_all_contents(path)?;
+        std::fs::remove_dir(path)?;
+        Ok(size)
+    } else {
+        remove_file(path, metadata)?;
+        Ok(if metadata.is_file() { metadata.len() } else { 0 })
+    }
+}
+
+/// Sets the modification time of a file or directory.
+///
+/// On Unix, this function uses *utimensat* which never opens the file, on Windows, this function
+/// uses *SetFileTime*.
+///
+/// # Arguments
+///
+/// * `path`: the file or directory to update.
+/// * `time`: the new modification time.
+///
+/// returns: Result<(), Error>
+///
+/// # Errors
+///
+/// Returns an [Error](std::io::Error) if the file couldn't be opened or its modification time
+/// couldn't be changed.
+pub fn set_modified_time<T: AsRef<std::path::Path>>(path: T, time: std::time::SystemTime) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        unix::set_modified_time(path.as_ref(), time)
+    }
+    #[cfg(windows)]
+    {
+        windows::set_modified_time(path.as_ref(), time)
+    }
+}
+
+/// Creates an empty file if it does not exist, otherwise updates its modification time to now.
+///
+/// # Arguments
+///
+/// * `path`: the file to touch.
+///
+/// returns: Result<(), Error>
+///
+/// # Errors
+///
+/// Returns an [Error](std::io::Error) if the file couldn't be created or its modification time
+/// couldn't be changed.
+pub fn touch<T: AsRef<std::path::Path>>(path: T) -> std::io::Result<()> {
+    let path = path.as_ref();
+    if std::fs::symlink_metadata(path).is_err() {
+        std::fs::OpenOptions::new().write(true).create(true).truncate(false).open(path)?;
+        return Ok(());
+    }
+    set_modified_time(path, std::time::SystemTime::now())
+}
+
+/// Returns a path which does not exist yet, based on a desired path.
+///
+/// If `desired` does not exist it is returned as is, otherwise " (n)" is inserted before the
+/// extensions of the file name, with n starting at 1 and incremented until the path does not exist.
+/// All extensions are kept, such that "a.tar.gz" becomes "a (1).tar.gz", and the leading dot of
+/// hidden unix files isn't considered as an extension.
+///
+/// # Arguments
+///
+/// * `desired`: the path to start from.
+///
+/// returns: PathBuf
+pub fn available_filename<T: AsRef<std::path::Path>>(desired: T) -> std::path::PathBuf {
+    let desired = desired.as_ref();
+    if std::fs::symlink_metadata(desired).is_err() {
+        return desired.into();
+    }
+    let name = desired.file_name().unwrap_or_default();
+    //File names which aren't valid unicode are only suffixed.
+    let (stem, ext) = match name.to_str() {
+        Some(v) => split_extensions(v),
+        None => (name, std::ffi::OsStr::new(""))
+    };
+    let mut n: u64 = 1;
+    loop {
+        let mut file_name = stem.to_os_string();
+        file_name.push(format!(" ({})", n));
+        file_name.push(ext);
+        let path = desired.with_file_name(file_name);
+        if std::fs::symlink_metadata(&path).is_err() {
+            return path;
+        }
+        n += 1;
+    }
+}
+
+fn split_extensions(name: &str) -> (&std::ffi::OsStr, &std::ffi::OsStr) {
+    let start = if name.starts_with('.') { 1 } else { 0 };
+    let (stem, ext) = match name[start..].find('.') {
+        Some(pos) => name.split_at(start + pos),
+        None => (name, "")
+    };
+    (stem.as_ref(), ext.as_ref())
+}
+
+/// Reads the entire content of a text file into a string, stripping any byte order mark.
+///
+/// UTF-8, UTF-16LE and UTF-16BE byte order marks are detected; UTF-16 content is decoded to UTF-8.
+/// Files without a byte order mark are read as UTF-8.
+///
+/// # Arguments
+///
+/// * `path`: the file to read.
+///
+/// returns: Result<String, Error>
+///
+/// # Errors
+///
+/// Returns an [Error](std::io::Error) if the file couldn't be read or if its content isn't valid
+/// in the detected encoding.
+pub fn read_to_string_bom<T: AsRef<std::path::Path>>(path: T) -> std::io::Result<String> {
+    decode_bom(std::fs::read(path)?)
+}
+
+fn decode_utf16<F: Fn([u8; 2]) -> u16>(bytes: &[u8], f: F) -> std::io::Result<String> {
+    let chunks = bytes.chunks_exact(2);
+    if !chunks.remainder().is_empty() {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "truncated UTF-16 data"));
+    }
+    let v: Vec<u16> = chunks.map(|v| f([v[0], v[1]])).collect();
+    String::from_utf16(&v).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+fn decode_bom(mut bytes: Vec<u8>) -> std::io::Result<String> {
+    if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) {
+        bytes.drain(..3);
+    } else if bytes.starts_with(&[0xFF, 0xFE]) {
+        return decode_utf16(&bytes[2..], u16::from_le_bytes);
+    } else if bytes.starts_with(&[0xFE, 0xFF]) {
+        return decode_utf16(&bytes[2..], u16::from_be_bytes);
+    }
+    String::from_utf8(bytes).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+/// Checks if a path is the same as or is located inside another path.
+///
+/// Both paths are made absolute and canonicalized before being compared component-wise, such
+/// that "a2" is not considered to be inside "a". For paths which don't exist yet, the longest
+/// existing prefix is canonicalized, resolving symbolic links and '..' like the system does, and
+/// only the remaining components are normalized lexically. This includes paths below a regular
+/// file, ex: "file.txt/x".
+///
+/// # Arguments
+///
+/// * `child`: the path to check.
+/// * `ancestor`: the path which may contain `child`.
+///
+/// returns: Result<bool, Error>
+///
+/// # Errors
+///
+/// Returns an [Error](std::io::Error) if one of the paths couldn't be made absolute.
+pub fn is_subpath<P: AsRef<std::path::Path>, Q: AsRef<std::path::Path>>(child: P, ancestor: Q) -> std::io::Result<bool> {
+    Ok(normalize_path(child.as_ref())?.starts_with(normalize_path(ancestor.as_ref())?))
+}
+
+fn normalize_path(path: &std::path::Path) -> std::io::Result<std::path::PathBuf> {
+    use std::io::ErrorKind;
+    use std::path::Component;
+    let path = match path.is_absolute() {
+        true => path.to_path_buf(),
+        false => std::env::current_dir()?.join(path)
+    };
+    let components: Vec<Component> = path.components().collect();
+    //Canonicalize the longest existing prefix such that symbolic links followed by '..' are
+    // resolved by the system; only the part which doesn't exist yet is normalized lexically.
+    for len in (1..=components.len()).rev() {
+        let prefix: std::path::PathBuf = components[..len].iter().collect();
+        let mut normalized = match canonicalize_no_unc(&prefix) {
+            Ok(v) => v,
+            //A prefix below a regular file does not exist either.
+            Err(e) if matches!(e.kind(), ErrorKind::NotFound | ErrorKind::NotADirectory) => continue,
+            Err(e) => return Err(e)
+        };
+        for component in &components[len..] {
+            match component {
+                Component::CurDir => (),
+                Component::ParentDir => {
+                    normalized.pop();
+                }
+                v => normalized.push(v)
+            }
+        }
+        return Ok(normalized);
+    }
+    Err(std::io::Error::new(std::io::ErrorKind::NotFound, "no existing ancestor"))
+}
 
 /// Extension trait for [Path](std::path::Path) for common functionality in BP3D software.
 pub trait PathExt {
     /// Ensures the given extension is present on a [Path](std::path::Path). Reallocates a new
     /// [PathBuf](std::path::PathBuf) if no extension is present or that the extension is incorrect.
-    fn ensure_extension<S: AsRef<std::ffi::OsStr>>(&self, extension: S) -> std::borrow::Cow<std::path::Path>;
+    fn ensure_extension<S: AsRef<std::ffi::OsStr>>(&self, extension: S) -> std::borrow::Cow<'_, std::path::Path>;
 
     /// Converts this path to an absolute path.
     ///
@@ -61,7 +340,7 @@ pub trait PathExt {
 }
 
 impl PathExt for std::path::Path {
-    fn ensure_extension<S: AsRef<std::ffi::OsStr>>(&self, extension: S) -> std::borrow::Cow<std::path::Path> {
+    fn ensure_extension<S: AsRef<std::ffi::OsStr>>(&self, extension: S) -> std::borrow::Cow<'_, std::path::Path> {
         if let Some(ext) = self.extension() {
             if ext == extension.as_ref() {
                 self.into()
@@ -82,9 +361,13 @@ impl PathExt for std::path::Path {
 
 #[cfg(test)]
 mod tests {
-    use crate::fs::PathExt;
+    use crate::fs::{
+        available_filename, canonicalize_no_unc, is_subpath, read_to_string_bom, remove,
+        remove_dir_all_contents, same_file, set_modified_time, touch, PathExt,
+    };
     use std::borrow::Cow;
     use std::path::Path;
+    use std::time::{Duration, SystemTime};
 
     #[test]
     fn basic() {
@@ -101,4 +384,156 @@ mod tests {
         assert_eq!(&no_ext_corrected, Path::new("myfile.bpx"));
         assert_eq!(&correct_ext_corrected, Path::new("myfile.bpx"));
     }
+
+    #[test]
+    fn same_file_check() {
+        let dir = std::env::temp_dir().join("bp3d-os-test-same-file");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a.txt"), b"a").unwrap();
+        std::fs::write(dir.join("b.txt"), b"a").unwrap();
+        let _ = std::fs::remove_file(dir.join("c.txt"));
+        std::fs::hard_link(dir.join("a.txt"), dir.join("c.txt")).unwrap();
+        assert!(same_file(dir.join("a.txt"), dir.join("a.txt")).unwrap());
+        assert!(same_file(dir.join("a.txt"), dir.join("c.txt")).unwrap());
+        assert!(same_file(dir.join("a.txt"), dir.join(".").join("a.txt")).unwrap());
+        assert!(!same_file(dir.join("a.txt"), dir.join("b.txt")).unwrap());
+        assert!(same_file(dir.join("d.txt"), dir.join("a.txt")).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn remove_tree() {
+        let dir = std::env::temp_dir().join("bp3d-os-test-remove-tree");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("a").join("b")).unwrap();
+        std::fs::write(dir.join("file.bin"), [0; 16]).unwrap();
+        std::fs::write(dir.join("a").join("file.bin"), [0; 32]).unwrap();
+        std::fs::write(dir.join("a").join("b").join("file.bin"), [0; 64]).unwrap();
+        let readonly = dir.join("a").join("b").join("readonly.bin");
+        std::fs::write(&readonly, [0; 128]).unwrap();
+        let mut perms = std::fs::metadata(&readonly).unwrap().permissions();
+        perms.set_readonly(true);
+        std::fs::set_permissions(&readonly, perms).unwrap();
+        assert_eq!(remove(dir.join("a").join("b")).unwrap(), 192);
+        assert!(!dir.join("a").join("b").exists());
+        assert_eq!(remove_dir_all_contents(&dir).unwrap(), 48);
+        assert!(dir.is_dir());
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+        assert_eq!(remove(&dir).unwrap(), 0);
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn canonicalize() {
+        let path = canonicalize_no_unc("Cargo.toml").unwrap();
+        assert!(path.is_absolute());
+        assert!(path.is_file());
+        assert!(same_file(&path, "Cargo.toml").unwrap());
+        #[cfg(windows)]
+        assert!(!path.as_os_str().to_string_lossy().starts_with(r"\\?\"));
+    }
+
+    #[test]
+    fn touch_file() {
+        let dir = std::env::temp_dir().join("bp3d-os-test-touch");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("file.txt");
+        touch(&file).unwrap();
+        assert!(file.is_file());
+        assert_eq!(std::fs::metadata(&file).unwrap().len(), 0);
+        std::fs::write(&file, b"content").unwrap();
+        //Use a whole number of seconds as not all file systems store sub-second times.
+        let now = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap();
+        let past = SystemTime::UNIX_EPOCH + Duration::from_secs(now.as_secs() - 3600);
+        set_modified_time(&file, past).unwrap();
+        assert_eq!(std::fs::metadata(&file).unwrap().modified().unwrap(), past);
+        touch(&file).unwrap();
+        assert!(std::fs::metadata(&file).unwrap().modified().unwrap() > past);
+        assert_eq!(std::fs::read(&file).unwrap(), b"content");
+        set_modified_time(&dir, past).unwrap();
+        assert_eq!(std::fs::metadata(&dir).unwrap().modified().unwrap(), past);
+        #[cfg(unix)]
+        {
+            //Touching a named pipe must not block waiting for a writer.
+            use std::os::unix::ffi::OsStrExt;
+            let fifo = dir.join("fifo");
+            let path = std::ffi::CString::new(fifo.as_os_str().as_bytes()).unwrap();
+            assert_eq!(unsafe { libc::mkfifo(path.as_ptr(), 0o644) }, 0);
+            let (sender, receiver) = std::sync::mpsc::channel();
+            std::thread::spawn(move || sender.send(touch(&fifo).is_ok()));
+            assert_eq!(receiver.recv_timeout(Duration::from_secs(5)), Ok(true));
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn available_name() {
+        let dir = std::env::temp_dir().join("bp3d-os-test-available-filename");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        assert_eq!(available_filename(dir.join("a.txt")), dir.join("a.txt"));
+        for name in ["a.txt", "a.tar.gz", "a", ".hidden", ".config.toml", "b.txt", "b (1).txt"].iter() {
+            std::fs::write(dir.join(name), b"").unwrap();
+        }
+        assert_eq!(available_filename(dir.join("a.txt")), dir.join("a (1).txt"));
+        assert_eq!(available_filename(dir.join("a.tar.gz")), dir.join("a (1).tar.gz"));
+        assert_eq!(available_filename(dir.join("a")), dir.join("a (1)"));
+        assert_eq!(available_filename(dir.join(".hidden")), dir.join(".hidden (1)"));
+        assert_eq!(available_filename(dir.join(".config.toml")), dir.join(".config (1).toml"));
+        assert_eq!(available_filename(dir.join("b.txt")), dir.join("b (2).txt"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn bom() {
+        let dir = std::env::temp_dir().join("bp3d-os-test-bom");
+        std::fs::create_dir_all(&dir).unwrap();
+        let text = "key = \"välue\"";
+        let utf16le: Vec<u8> = text.encode_utf16().flat_map(|v| v.to_le_bytes()).collect();
+        let utf16be: Vec<u8> = text.encode_utf16().flat_map(|v| v.to_be_bytes()).collect();
+        let files = [
+            ("none.txt", text.as_bytes().to_vec()),
+            ("utf8.txt", [&[0xEF, 0xBB, 0xBF], text.as_bytes()].concat()),
+            ("utf16le.txt", [&[0xFF, 0xFE][..], &utf16le].concat()),
+            ("utf16be.txt", [&[0xFE, 0xFF][..], &utf16be].concat()),
+        ];
+        for (name, data) in files.iter() {
+            std::fs::write(dir.join(name), data).unwrap();
+            assert_eq!(read_to_string_bom(dir.join(name)).unwrap(), text);
+        }
+        std::fs::write(dir.join("invalid.txt"), [0xFF, 0xFE, 0x00]).unwrap();
+        assert!(read_to_string_bom(dir.join("invalid.txt")).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn subpath() {
+        let dir = std::env::temp_dir().join("bp3d-os-test-subpath");
+        std::fs::create_dir_all(dir.join("a")).unwrap();
+        std::fs::create_dir_all(dir.join("a2")).unwrap();
+        assert!(is_subpath(dir.join("a"), dir.join("a")).unwrap());
+        assert!(is_subpath(dir.join("a").join("b"), dir.join("a")).unwrap());
+        assert!(is_subpath(dir.join("a").join("b").join("c"), dir.join("a").join("b")).unwrap());
+        assert!(is_subpath(dir.join("a").join("."), dir.join("a")).unwrap());
+        assert!(!is_subpath(dir.join("a2"), dir.join("a")).unwrap());
+        assert!(!is_subpath(dir.join("a"), dir.join("a").join("b")).unwrap());
+        assert!(!is_subpath(dir.join("a").join("b").join("..").join(".."), dir.join("a")).unwrap());
+        assert!(is_subpath("src", std::env::current_dir().unwrap()).unwrap());
+        std::fs::write(dir.join("a").join("file.txt"), b"").unwrap();
+        assert!(is_subpath(dir.join("a").join("file.txt").join("x"), dir.join("a")).unwrap());
+        assert!(!is_subpath(dir.join("a").join("file.txt").join("x"), dir.join("a2")).unwrap());
+        #[cfg(unix)]
+        {
+            //a/link/.. resolves to other, not to a.
+            std::fs::create_dir_all(dir.join("other").join("deep")).unwrap();
+            let _ = std::fs::remove_file(dir.join("a").join("link"));
+            std::os::unix::fs::symlink(dir.join("other").join("deep"), dir.join("a").join("link")).unwrap();
+            assert!(!is_subpath(dir.join("a").join("link").join("..").join("x"), dir.join("a")).unwrap());
+            assert!(is_subpath(dir.join("a").join("link").join("..").join("x"), dir.join("other")).unwrap());
+            assert!(is_subpath(dir.join("a").join("link").join("x"), dir.join("other")).unwrap());
+            assert!(is_subpath(dir.join("a").join("missing").join("..").join("x"), dir.join("a")).unwrap());
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
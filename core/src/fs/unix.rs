@@ -28,8 +28,12 @@
 
 use std::io::{Error, ErrorKind, Result};
 use std::ffi::OsStr;
+use std::ffi::CString;
+use std::fs::Metadata;
 use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::MetadataExt;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 /// Hides the given path in the current platform's file explorer.
 ///
@@ -122,3 +126,55 @@ pub fn is_hidden<T: AsRef<Path>>(path: T) -> bool {
     }
     false
 }
+
+/// Checks if two paths point to the same file or directory.
+///
+/// Two paths are considered the same file if they have the same device and inode numbers. This
+/// function follows symbolic links.
+///
+/// # Arguments
+///
+/// * `a`: the first path to check.
+/// * `b`: the second path to check.
+///
+/// returns: Result<bool, Error>
+///
+/// # Errors
+///
+/// Returns an [Error](Error) if the metadata of one of the paths couldn't be obtained.
+pub fn same_file<P: AsRef<Path>, Q: AsRef<Path>>(a: P, b: Q) -> Result<bool> {
+    let a = std::fs::metadata(a)?;
+    let b = std::fs::metadata(b)?;
+    Ok(a.dev() == b.dev() && a.ino() == b.ino())
+}
+
+pub(super) fn remove_file(path: &Path, _: &Metadata) -> Result<()> {
+    std::fs::remove_file(path)
+}
+
+pub(super) fn set_modified_time(path: &Path, time: SystemTime) -> Result<()> {
+    //utimensat works on the path directly: opening the file would block on named pipes.
+    let path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
+    let (sec, nsec) = match time.duration_since(SystemTime::UNIX_EPOCH) {
+        Ok(v) => (v.as_secs() as libc::time_t, v.subsec_nanos() as libc::c_long),
+        Err(e) => {
+            let v = e.duration();
+            match v.subsec_nanos() {
+                0 => (-(v.as_secs() as libc::time_t), 0),
+                nanos => (-(v.as_secs() as libc::time_t) - 1, (1_000_000_000 - nanos) as libc::c_long),
+            }
+        }
+    };
+    let times = [
+        //Keep the access time unchanged.
+        libc::timespec { tv_sec: 0, tv_nsec: libc::UTIME_OMIT },
+        libc::timespec { tv_sec: sec, tv_nsec: nsec },
+    ];
+    unsafe {
+        if libc::utimensat(libc::AT_FDCWD, path.as_ptr(), times.as_ptr(), 0) != 0 {
+            return Err(Error::last_os_error());
+        }
+    }
+    Ok(())
+}
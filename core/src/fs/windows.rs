@@ -26,12 +26,21 @@
 // NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use std::fs::{Metadata, OpenOptions};
 use std::io::{Error, ErrorKind, Result};
 use std::os::windows::ffi::OsStrExt;
+use std::os::windows::fs::{FileTypeExt, MetadataExt, OpenOptionsExt};
+use std::os::windows::io::AsRawHandle;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use windows_sys::Win32::Storage::FileSystem::GetFileInformationByHandle;
+use windows_sys::Win32::Storage::FileSystem::BY_HANDLE_FILE_INFORMATION;
+use windows_sys::Win32::Storage::FileSystem::FILE_FLAG_BACKUP_SEMANTICS;
+use windows_sys::Win32::Storage::FileSystem::FILE_WRITE_ATTRIBUTES;
 use windows_sys::Win32::Storage::FileSystem::SetFileAttributesW;
 use windows_sys::Win32::Storage::FileSystem::GetFileAttributesW;
 use windows_sys::Win32::Storage::FileSystem::FILE_ATTRIBUTE_HIDDEN;
+use windows_sys::Win32::Storage::FileSystem::FILE_ATTRIBUTE_READONLY;
 use windows_sys::Win32::Storage::FileSystem::INVALID_FILE_ATTRIBUTES;
 
 /// Hides the given path in the current platform's file explorer.
@@ -99,7 +108,10 @@ pub fn unhide<T: AsRef<Path>>(path: T) -> Result<()> {
 /// Converts a path to an absolute path.
 ///
 /// This function will try it's best to avoid using UNC paths which aren't supported by all
-/// applications.
+/// applications: the path is canonicalized, resolving symbolic links, and the `\\?\` prefix is
+/// stripped, or the `\\?\UNC\` prefix is converted back to `\\`, when the resulting path is still
+/// valid without it. The prefix is kept for paths longer than MAX_PATH or containing reserved
+/// names.
 ///
 /// # Arguments
 ///
@@ -139,3 +151,67 @@ pub fn is_hidden<T: AsRef<Path>>(path: T) -> bool {
     }
     false
 }
+
+fn get_file_id(path: &Path) -> Result<(u32, u64)> {
+    //FILE_FLAG_BACKUP_SEMANTICS is required to open directories.
+    let file = OpenOptions::new()
+        .access_mode(0)
+        .custom_flags(FILE_FLAG_BACKUP_SEMANTICS)
+        .open(path)?;
+    unsafe {
+        let mut info: BY_HANDLE_FILE_INFORMATION = std::mem::zeroed();
+        if GetFileInformationByHandle(file.as_raw_handle() as _, &mut info) == 0 {
+            return Err(Error::last_os_error());
+        }
+        let index = (info.nFileIndexHigh as u64) << 32 | info.nFileIndexLow as u64;
+        Ok((info.dwVolumeSerialNumber, index))
+    }
+}
+
+/// Checks if two paths point to the same file or directory.
+///
+/// Two paths are considered the same file if they have the same volume serial number and file
+/// index. This function follows symbolic links.
+///
+/// # Arguments
+///
+/// * `a`: the first path to check.
+/// * `b`: the second path to check.
+///
+/// returns: Result<bool, Error>
+///
+/// # Errors
+///
+/// Returns an [Error](Error) if one of the paths couldn't be opened.
+pub fn same_file<P: AsRef<Path>, Q: AsRef<Path>>(a: P, b: Q) -> Result<bool> {
+    Ok(get_file_id(a.as_ref())? == get_file_id(b.as_ref())?)
+}
+
+pub(super) fn remove_file(path: &Path, metadata: &Metadata) -> Result<()> {
+    let attrs = metadata.file_attributes();
+    if attrs & FILE_ATTRIBUTE_READONLY != 0 {
+        //Windows refuses to delete read-only files.
+        let mut file: Vec<u16> = path.as_os_str().encode_wide().collect();
+        file.push(0x0000);
+        unsafe {
+            if SetFileAttributesW(file.as_ptr(), attrs & !FILE_ATTRIBUTE_READONLY) == 0 {
+                return Err(Error::last_os_error());
+            }
+        }
+    }
+    if metadata.file_type().is_symlink_dir() {
+        std::fs::remove_dir(path)
+    } else {
+        std::fs::remove_file(path)
+    }
+}
+
+pub(super) fn set_modified_time(path: &Path, time: SystemTime) -> Result<()> {
+    //SetFileTime requires FILE_WRITE_ATTRIBUTES and FILE_FLAG_BACKUP_SEMANTICS is required to
+    //open directories.
+    OpenOptions::new()
+        .access_mode(FILE_WRITE_ATTRIBUTES)
+        .custom_flags(FILE_FLAG_BACKUP_SEMANTICS)
+        .open(path)?
+        .set_modified(time)
+}
@@ -26,7 +26,7 @@
 // NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use crate::open::Url;
+use crate::open::{OpenVerb, Url};
 use std::path::Path;
 
 pub fn open(_: &Url) -> bool {
@@ -34,7 +34,25 @@ pub fn open(_: &Url) -> bool {
     false
 }
 
+pub fn open_with_verb(url: &Url, verb: OpenVerb) -> bool {
+    match verb {
+        OpenVerb::Open => open(url),
+        //Unsupported on iOS
+        _ => false
+    }
+}
+
+pub fn open_all(urls: &[Url]) -> bool {
+    //Unsupported on iOS, see open.
+    urls.is_empty()
+}
+
 pub fn show_in_files<'a, I: Iterator<Item = &'a Path>>(_: I) -> bool {
     //Unsupported on iOS
     false
 }
+
+pub fn open_terminal(_: &Path) -> bool {
+    //Unsupported on iOS
+    false
+}
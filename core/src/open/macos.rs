@@ -35,8 +35,9 @@ use objc::sel;
 use objc::sel_impl;
 use objc::runtime::{BOOL, NO, Object};
 use std::path::Path;
+use std::process::Command;
 use crate::fs::PathExt;
-use crate::open::Url;
+use crate::open::{OpenVerb, Url};
 
 const NS_UTF8_STRING_ENCODING: c_ulong = 4;
 
@@ -64,7 +65,47 @@ pub fn open(url: &Url) -> bool {
     }
 }
 
-pub fn show_in_files<'a, I: Iterator<Item = &'a Path>>(iter: I) -> bool {
+pub fn open_with_verb(url: &Url, verb: OpenVerb) -> bool {
+    let path = Path::new(url.path());
+    match verb {
+        OpenVerb::Open => open(url),
+        _ if !url.is_path() => false,
+        OpenVerb::Edit => Command::new("open").arg("-t").arg(path).status().map(|v| v.success()).unwrap_or(false),
+        OpenVerb::Print => Command::new("lp").arg(path).status().map(|v| v.success()).unwrap_or(false),
+        OpenVerb::Explore => crate::open::reveal(path)
+    }
+}
+
+pub fn open_all(urls: &[Url]) -> bool {
+    if urls.is_empty() {
+        return true;
+    }
+    let nsstring = class!(NSString);
+    let nsurl = class!(NSURL);
+    let nsarray = class!(NSArray);
+    let nsworkspace = class!(NSWorkspace);
+    unsafe {
+        //URLs which couldn't be converted are skipped such that all other URLs are still opened.
+        let nsurls: Vec<*mut Object> = urls.iter().filter_map(|v| v.to_os_str().ok()).map(|v| {
+            let mut str: *mut Object = msg_send![nsstring, alloc];
+            str = msg_send![str, initWithBytes: v.as_bytes().as_ptr() length: v.len() as c_ulong encoding: NS_UTF8_STRING_ENCODING];
+            let url: *mut Object = msg_send![nsurl, URLWithString: str];
+            let _: () = msg_send![str, release]; // release string (we used alloc)
+            url
+        }).filter(|v| !v.is_null()).collect();
+        if nsurls.is_empty() {
+            return false;
+        }
+        let arr: *mut Object = msg_send![nsarray, arrayWithObjects: nsurls.as_ptr() count: nsurls.len() as c_ulong];
+        let workspace: *mut Object = msg_send![nsworkspace, sharedWorkspace];
+        let null: *mut Object = std::ptr::null_mut();
+        // Use the default application for each URL (NSWorkspaceLaunchDefault).
+        let res: BOOL = msg_send![workspace, openURLs: arr withAppBundleIdentifier: null options: 0 as c_ulong additionalEventParamDescriptor: null launchIdentifiers: null];
+        res != NO && nsurls.len() == urls.len()
+    }
+}
+
+fn select_in_files<'a, I: Iterator<Item = &'a Path>>(iter: I, run_loop: bool) -> bool {
     let nsthread = class!(NSThread);
     let nsrunloop = class!(NSRunLoop);
     let nsdate = class!(NSDate);
@@ -100,11 +141,42 @@ pub fn show_in_files<'a, I: Iterator<Item = &'a Path>>(iter: I) -> bool {
             let _: () = msg_send![url, release];
         }
         let _: () = msg_send![arr, release]; // release array
-        //Create a date of 1 sec in the future
-        let runloop: *mut Object = msg_send![nsrunloop, mainRunLoop];
-        let date: *mut Object = msg_send![nsdate, dateWithTimeIntervalSinceNow: 1.0 as c_double];
-        let _: () = msg_send![runloop, runUntilDate: date];
-        let _: () = msg_send![date, release];
+        if run_loop {
+            //Create a date of 1 sec in the future
+            let runloop: *mut Object = msg_send![nsrunloop, mainRunLoop];
+            let date: *mut Object = msg_send![nsdate, dateWithTimeIntervalSinceNow: 1.0 as c_double];
+            let _: () = msg_send![runloop, runUntilDate: date];
+            let _: () = msg_send![date, release];
+        }
     }
     true
 }
+
+pub fn show_in_files<'a, I: Iterator<Item = &'a Path>>(iter: I) -> bool {
+    select_in_files(iter, true)
+}
+
+pub fn show_in_files_async<'a, I: Iterator<Item = &'a Path>>(iter: I) -> bool {
+    select_in_files(iter, false)
+}
+
+pub fn open_terminal(dir: &Path) -> bool {
+    let res = Command::new("open")
+        .args(["-a", "Terminal"])
+        .arg(dir)
+        .status();
+    res.map(|v| v.success()).unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{show_in_files, show_in_files_async};
+    use std::path::Path;
+
+    #[test]
+    fn requires_main_thread() {
+        //Rust tests never run on the main thread.
+        assert!(!show_in_files(std::iter::once(Path::new("."))));
+        assert!(!show_in_files_async(std::iter::once(Path::new("."))));
+    }
+}
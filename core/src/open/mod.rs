@@ -83,6 +83,68 @@ pub fn show_in_files<'a, I: Iterator<Item = &'a std::path::Path>>(iter: I) -> bo
     _impl::show_in_files(iter)
 }
 
+/// Open a file explorer selecting the different files given as iterator without waiting for the
+/// file explorer to show up.
+///
+/// Returns true if the operation has succeeded.
+///
+/// # Platform specific behavior
+///
+/// - On macOS, this function calls *activateFileViewerSelectingURLs* in *NSWorkspace* but unlike
+///   [show_in_files] it does not call *runUntilDate* in *NSRunLoop*. The request is delivered
+///   the next time the caller's own main run loop runs, which makes this function suitable for
+///   applications already running an event loop. **This function still returns false if called
+///   from a different thread than the main thread**.
+///
+/// - On Linux and most other unix systems, this function is identical to [show_in_files]: it
+///   blocks until the file explorer answers the dbus call or until the timeout set with
+///   [set_dbus_timeout] expires.
+///
+/// - On all other platforms, [show_in_files] does not block, so this function is identical to
+///   [show_in_files].
+pub fn show_in_files_async<'a, I: Iterator<Item = &'a std::path::Path>>(iter: I) -> bool {
+    #[cfg(target_os = "macos")]
+    {
+        _impl::show_in_files_async(iter)
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        _impl::show_in_files(iter)
+    }
+}
+
+/// Reveals a single file or directory in the file explorer.
+///
+/// This function first attempts to select the path using [show_in_files]. If that fails, for
+/// example because the platform does not support selecting files, this function opens the
+/// directory containing the path using [open] instead.
+///
+/// Returns true if the operation has succeeded.
+///
+/// # Arguments
+///
+/// * `path`: the path to reveal.
+///
+/// returns: bool
+pub fn reveal<T: AsRef<std::path::Path>>(path: T) -> bool {
+    reveal_with(path.as_ref(), show_in_files, |v| open(v))
+}
+
+fn reveal_with<'a, S, O>(path: &'a std::path::Path, show_in_files: S, open: O) -> bool
+where
+    S: FnOnce(std::iter::Once<&'a std::path::Path>) -> bool,
+    O: FnOnce(&std::path::Path) -> bool,
+{
+    if show_in_files(std::iter::once(path)) {
+        return true;
+    }
+    let path = match crate::fs::get_absolute_path(path) {
+        Ok(v) => v,
+        Err(_) => return false,
+    };
+    open(path.parent().unwrap_or(&path))
+}
+
 /// Opens an URL using the default associated app for the URL scheme.
 ///
 /// Returns true if the operation has succeeded.
@@ -107,3 +169,150 @@ pub fn show_in_files<'a, I: Iterator<Item = &'a std::path::Path>>(iter: I) -> bo
 pub fn open<'a, T: Into<Url<'a>>>(url: T) -> bool {
     _impl::open(&url.into())
 }
+
+/// The action to perform when opening an URL with [open_with_verb].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OpenVerb {
+    /// Opens the URL with the default associated app, same as [open].
+    Open,
+
+    /// Opens the file for editing.
+    Edit,
+
+    /// Prints the file.
+    Print,
+
+    /// Shows the file or directory in the file explorer.
+    Explore,
+}
+
+/// Opens an URL performing the given action.
+///
+/// Returns true if the operation has succeeded. Returns false if the action is not supported
+/// for the given URL on the current platform; only [Open](OpenVerb::Open) is supported for URLs
+/// which aren't paths outside of Windows.
+///
+/// # Platform specific behavior
+///
+/// - On Windows, this function calls *ShellExecuteW* with the "open", "edit", "print" or "explore"
+///   operation.
+///
+/// - On macOS, [Edit](OpenVerb::Edit) runs *open -t* to use the default text editor,
+///   [Print](OpenVerb::Print) runs *lp* and [Explore](OpenVerb::Explore) calls [reveal].
+///
+/// - On iOS, only [Open](OpenVerb::Open) is supported which currently returns false.
+///
+/// - On Linux and most other unix systems, [Edit](OpenVerb::Edit) is unsupported,
+///   [Print](OpenVerb::Print) runs *lp* and [Explore](OpenVerb::Explore) calls [reveal].
+///
+/// # Arguments
+///
+/// * `url`: the URL to open.
+/// * `verb`: the action to perform.
+///
+/// returns: bool
+pub fn open_with_verb<'a, T: Into<Url<'a>>>(url: T, verb: OpenVerb) -> bool {
+    _impl::open_with_verb(&url.into(), verb)
+}
+
+/// Opens multiple URLs at once using the default associated app for each URL scheme.
+///
+/// Returns true if all URLs were opened. All URLs are attempted even if one of them fails.
+///
+/// # Platform specific behavior
+///
+/// - On macOS, this function passes all URLs at once to *openURLs* in *NSWorkspace*.
+///
+/// - On iOS, this function currently returns false unless no URL is given.
+///
+/// - On Windows, this function calls *ShellExecuteW* with the "open" operation for each URL.
+///
+/// - On Linux and most other unix systems, URLs to directories are batched in a single call to
+///   the dbus function *ShowFolders* in *org.freedesktop.FileManager1*, all other URLs are opened
+///   by executing *xdg-open* once per URL.
+///
+/// # Arguments
+///
+/// * `urls`: the URLs to open.
+///
+/// returns: bool
+pub fn open_all<'a, I: IntoIterator<Item = Url<'a>>>(urls: I) -> bool {
+    let urls: Vec<Url> = urls.into_iter().collect();
+    _impl::open_all(&urls)
+}
+
+/// Opens a terminal window with its working directory set to the given directory.
+///
+/// Returns true if the operation has succeeded.
+///
+/// # Platform specific behavior
+///
+/// - On macOS, this function runs *open -a Terminal* with the directory as argument.
+///
+/// - On iOS, this function always returns false because there is no terminal application.
+///
+/// - On Windows, this function attempts to start Windows Terminal (*wt*) and falls back to
+///   starting *cmd* in a new console.
+///
+/// - On Linux and most other unix systems, this function starts the first terminal emulator found
+///   in PATH among *x-terminal-emulator*, *gnome-terminal*, *konsole* and *xterm*. The terminal
+///   is started in a new session, detached from the calling process.
+///
+/// # Arguments
+///
+/// * `dir`: the directory to open the terminal at.
+///
+/// returns: bool
+pub fn open_terminal<T: AsRef<std::path::Path>>(dir: T) -> bool {
+    let dir = dir.as_ref();
+    if !dir.is_dir() {
+        return false;
+    }
+    _impl::open_terminal(dir)
+}
+
+/// Sets the maximum time to wait for the file explorer to answer dbus calls.
+///
+/// The default timeout is 5 seconds. When a dbus call times out, [open] and [open_all] fall back
+/// to *xdg-open*, [show_in_files] returns false and [reveal] opens the containing directory. A
+/// file explorer which is slow but not hung may still complete the request later, in which case
+/// the folder is shown twice.
+///
+/// # Platform specific behavior
+///
+/// - On Linux and most other unix systems, this sets the timeout of the calls to
+///   *org.freedesktop.FileManager1*.
+///
+/// - On all other platforms, dbus is not used and this function does nothing.
+///
+/// # Arguments
+///
+/// * `timeout`: the maximum time to wait for an answer.
+pub fn set_dbus_timeout(timeout: std::time::Duration) {
+    #[cfg(all(unix, not(any(target_vendor = "apple", target_os = "android"))))]
+    _impl::set_dbus_timeout(timeout);
+    #[cfg(not(all(unix, not(any(target_vendor = "apple", target_os = "android")))))]
+    let _ = timeout;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::open::reveal_with;
+    use std::cell::RefCell;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn reveal_fallback() {
+        let opened = RefCell::new(None);
+        let path = Path::new("Cargo.toml");
+        assert!(reveal_with(path, |_| true, |_| unreachable!()));
+        assert!(reveal_with(path, |_| false, |v| {
+            *opened.borrow_mut() = Some(PathBuf::from(v));
+            true
+        }));
+        let expected = crate::fs::get_absolute_path(std::env::current_dir().unwrap()).unwrap();
+        assert_eq!(opened.into_inner(), Some(expected));
+        assert!(!reveal_with(path, |_| false, |_| false));
+        assert!(!reveal_with(Path::new("missing.file"), |_| false, |_| true));
+    }
+}
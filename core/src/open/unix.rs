@@ -26,13 +26,25 @@
 // NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use crate::open::Url;
-use std::ffi::OsStr;
+use crate::open::{OpenVerb, Url};
+use std::ffi::{OsStr, OsString};
+use std::os::unix::fs::PermissionsExt;
 use zbus::{blocking::Connection, dbus_proxy, Result};
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::os::unix::process::CommandExt;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
 use crate::fs::PathExt;
 
+//Timeout in milliseconds for dbus calls.
+static DBUS_TIMEOUT: AtomicU64 = AtomicU64::new(5000);
+
+pub fn set_dbus_timeout(timeout: Duration) {
+    DBUS_TIMEOUT.store(timeout.as_millis() as u64, Ordering::Relaxed);
+}
+
 #[dbus_proxy(default_service = "org.freedesktop.FileManager1", interface = "org.freedesktop.FileManager1", default_path = "/org/freedesktop/FileManager1")]
 trait FileManager {
     //This is what we want when the url is a path (file://) and a folder
@@ -42,7 +54,8 @@ trait FileManager {
     fn show_items(&self, uris: &[&str], startup_id: &str) -> Result<()>;
 }
 
-fn attempt_dbus_call(urls: &[&str], show_items: bool) -> bool {
+fn dbus_call(urls: &[String], show_items: bool) -> bool {
+    let urls: Vec<&str> = urls.iter().map(|v| &**v).collect();
     let con = match Connection::session() {
         Ok(v) => v,
         Err(_) => return false,
@@ -52,17 +65,51 @@ fn attempt_dbus_call(urls: &[&str], show_items: bool) -> bool {
         Err(_) => return false,
     };
     let res = match show_items {
-        true => proxy.show_items(urls, "test"),
-        false => proxy.show_folders(urls, "test")
+        true => proxy.show_items(&urls, "test"),
+        false => proxy.show_folders(&urls, "test")
     };
     res.is_ok()
 }
 
-fn attempt_xdg_open(url: &OsStr) -> bool {
-    let res = Command::new("xdg-open")
-        .args([url])
+//Returns None if the call timed out.
+fn call_with_timeout<F: FnOnce() -> bool + Send + 'static>(f: F, timeout: Duration) -> Option<bool> {
+    //zbus blocking calls cannot be given a timeout, so run the call on a separate thread and
+    //give up waiting if the file manager does not answer in time.
+    //On timeout the thread is not stopped: it stays blocked until the dbus call returns, which is
+    //bounded by the reply timeout of the bus daemon or by the file manager exiting. The result is
+    //then discarded as the receiver is gone.
+    let (sender, receiver) = mpsc::channel();
+    let res = std::thread::Builder::new().spawn(move || {
+        let _ = sender.send(f());
+    });
+    if res.is_err() {
+        return Some(false);
+    }
+    match receiver.recv_timeout(timeout) {
+        Ok(v) => Some(v),
+        Err(RecvTimeoutError::Timeout) => None,
+        //The thread panicked before sending its result.
+        Err(RecvTimeoutError::Disconnected) => Some(false)
+    }
+}
+
+//Returns None if the call timed out.
+fn attempt_dbus_call(urls: &[&str], show_items: bool) -> Option<bool> {
+    let urls: Vec<String> = urls.iter().map(|v| v.to_string()).collect();
+    let timeout = Duration::from_millis(DBUS_TIMEOUT.load(Ordering::Relaxed));
+    call_with_timeout(move || dbus_call(&urls, show_items), timeout)
+}
+
+fn attempt_command(program: &str, arg: &OsStr) -> bool {
+    let res = Command::new(program)
+        .args([arg])
         .output();
-    res.is_ok()
+    //xdg-open reports failures (ex: no application found) through its exit code.
+    res.map(|v| v.status.success()).unwrap_or(false)
+}
+
+fn attempt_xdg_open(url: &OsStr) -> bool {
+    attempt_command("xdg-open", url)
 }
 
 pub fn open(url: &Url) -> bool {
@@ -74,14 +121,67 @@ pub fn open(url: &Url) -> bool {
     if !url.is_path() || !path.is_dir() {
         return attempt_xdg_open(&uri);
     }
-    let mut flag = match uri.to_str() {
+    let res = match uri.to_str() {
         Some(v) => attempt_dbus_call(&[v], false),
-        None => false
+        None => Some(false)
     };
-    if !flag {
-        flag = attempt_xdg_open(&uri);
+    match res {
+        Some(true) => true,
+        //Also fallback to xdg-open on timeout: the file manager may be hung.
+        Some(false) | None => attempt_xdg_open(&uri)
+    }
+}
+
+pub fn open_with_verb(url: &Url, verb: OpenVerb) -> bool {
+    let path = Path::new(url.path());
+    match verb {
+        OpenVerb::Open => open(url),
+        _ if !url.is_path() => false,
+        //There is no standard way to query the default text editor.
+        OpenVerb::Edit => false,
+        OpenVerb::Print => attempt_command("lp", path.as_os_str()),
+        OpenVerb::Explore => crate::open::reveal(path)
+    }
+}
+
+//Splits URLs into directories which can be opened with a single dbus call and URLs which must be
+//opened with xdg-open. URLs which couldn't be converted are skipped and counted.
+fn split_urls(urls: &[Url]) -> (Vec<String>, Vec<OsString>, usize) {
+    let mut folders = Vec::new();
+    let mut others = Vec::new();
+    let mut failed = 0;
+    for url in urls {
+        let uri = match url.to_os_str() {
+            Ok(v) => v,
+            Err(_) => {
+                failed += 1;
+                continue;
+            }
+        };
+        let folder = match url.is_path() && Path::new(url.path()).is_dir() {
+            true => uri.to_str().map(String::from),
+            false => None
+        };
+        match folder {
+            Some(v) => folders.push(v),
+            None => others.push(uri)
+        }
+    }
+    (folders, others, failed)
+}
+
+pub fn open_all(urls: &[Url]) -> bool {
+    let (folders, mut others, failed) = split_urls(urls);
+    if !folders.is_empty() {
+        let v: Vec<&str> = folders.iter().map(|v| &**v).collect();
+        match attempt_dbus_call(&v, false) {
+            Some(true) => (),
+            //Also fallback to xdg-open on timeout: the file manager may be hung.
+            Some(false) | None => others.extend(folders.into_iter().map(OsString::from))
+        }
     }
-    flag
+    //Every URL is still attempted when one fails.
+    others.iter().filter(|v| !attempt_xdg_open(v)).count() == 0 && failed == 0
 }
 
 pub fn show_in_files<'a, I: Iterator<Item = &'a Path>>(iter: I) -> bool {
@@ -91,7 +191,138 @@ pub fn show_in_files<'a, I: Iterator<Item = &'a Path>>(iter: I) -> bool {
         Err(_) => return false
     };
     match paths {
-        Some(v) => attempt_dbus_call(&v, true),
+        Some(v) => attempt_dbus_call(&v, true).unwrap_or(false),
         None => false
     }
 }
+
+const TERMINALS: &[&str] = &["x-terminal-emulator", "gnome-terminal", "konsole", "xterm"];
+
+fn find_terminal(path: &OsStr) -> Option<PathBuf> {
+    TERMINALS.iter().find_map(|name| {
+        std::env::split_paths(path)
+            .map(|v| v.join(name))
+            .find(|v| {
+                v.metadata()
+                    .map(|v| v.is_file() && v.permissions().mode() & 0o111 != 0)
+                    .unwrap_or(false)
+            })
+    })
+}
+
+pub fn open_terminal(dir: &Path) -> bool {
+    let terminal = match std::env::var_os("PATH").and_then(|v| find_terminal(&v)) {
+        Some(v) => v,
+        None => return false,
+    };
+    let mut command = Command::new(terminal);
+    command.current_dir(dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    //Detach the terminal with a double fork: the spawned child starts a new session, forks again
+    // and exits right away, so the terminal is re-parented to init which reaps it once it exits.
+    //This avoids keeping a thread waiting on the terminal for its whole lifetime. Exec failures
+    // of the grandchild are still reported by spawn.
+    unsafe {
+        command.pre_exec(|| {
+            if libc::setsid() == -1 {
+                return Err(std::io::Error::last_os_error());
+            }
+            match libc::fork() {
+                -1 => Err(std::io::Error::last_os_error()),
+                0 => Ok(()),
+                _ => libc::_exit(0)
+            }
+        });
+    }
+    let mut child = match command.spawn() {
+        Ok(v) => v,
+        Err(_) => return false
+    };
+    //Only reaps the intermediate child which exits immediately.
+    child.wait().map(|v| v.success()).unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{attempt_command, call_with_timeout, find_terminal, split_urls};
+    use crate::open::Url;
+    use std::convert::TryFrom;
+    use std::ffi::{OsStr, OsString};
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::Path;
+    use std::time::{Duration, Instant};
+
+    fn create_executable(path: &Path) {
+        std::fs::write(path, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[test]
+    fn terminal_selection() {
+        let root = std::env::temp_dir().join("bp3d-os-test-terminal-selection");
+        let _ = std::fs::remove_dir_all(&root);
+        let first = root.join("first");
+        let second = root.join("second");
+        std::fs::create_dir_all(&first).unwrap();
+        std::fs::create_dir_all(&second).unwrap();
+        let path = std::env::join_paths([&first, &second]).unwrap();
+        assert_eq!(find_terminal(&path), None);
+        create_executable(&second.join("xterm"));
+        assert_eq!(find_terminal(&path), Some(second.join("xterm")));
+        create_executable(&first.join("konsole"));
+        assert_eq!(find_terminal(&path), Some(first.join("konsole")));
+        std::fs::write(first.join("gnome-terminal"), "").unwrap();
+        assert_eq!(find_terminal(&path), Some(first.join("konsole")));
+        create_executable(&second.join("x-terminal-emulator"));
+        assert_eq!(find_terminal(&path), Some(second.join("x-terminal-emulator")));
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn command_exit_status() {
+        assert!(attempt_command("true", OsStr::new("https://rust-lang.org")));
+        assert!(!attempt_command("false", OsStr::new("https://rust-lang.org")));
+        assert!(!attempt_command("bp3d-os-missing-command", OsStr::new("https://rust-lang.org")));
+    }
+
+    #[test]
+    fn dbus_timeout() {
+        let start = Instant::now();
+        //Simulates a file manager which never answers.
+        let res = call_with_timeout(|| loop {
+            std::thread::sleep(Duration::from_secs(60));
+        }, Duration::from_millis(100));
+        assert_eq!(res, None);
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert_eq!(call_with_timeout(|| true, Duration::from_secs(5)), Some(true));
+        assert_eq!(call_with_timeout(|| false, Duration::from_secs(5)), Some(false));
+        assert_eq!(call_with_timeout(|| panic!(), Duration::from_secs(5)), Some(false));
+    }
+
+    #[test]
+    fn url_batching() {
+        let dir = std::env::temp_dir().join("bp3d-os-test-url-batching");
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("file.txt");
+        std::fs::write(&file, b"").unwrap();
+        let urls = [
+            Url::from(dir.as_path()),
+            Url::try_from("https://rust-lang.org").unwrap(),
+            Url::from(Path::new("bp3d-os-missing-file")),
+            Url::from(file.as_path()),
+            Url::from(dir.as_path()),
+        ];
+        let (folders, others, failed) = split_urls(&urls);
+        assert_eq!(failed, 1);
+        let dir_uri = Url::from(dir.as_path()).to_os_str().unwrap();
+        assert_eq!(folders, vec![dir_uri.to_str().unwrap().to_string(); 2]);
+        assert_eq!(others, vec![
+            OsString::from("https://rust-lang.org"),
+            Url::from(file.as_path()).to_os_str().unwrap()
+        ]);
+        assert_eq!(split_urls(&[]), (Vec::new(), Vec::new(), 0));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
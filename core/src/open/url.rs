@@ -46,9 +46,82 @@ impl<'a> Display for InvalidUrl<'a> {
 impl<'a> Error for InvalidUrl<'a> {}
 
 /// Represents an URL to be passed to the open function.
+///
+/// The components of an URL are given and stored in their percent-encoded form: components parsed
+/// from a string are kept as they are and a literal '%' must be given as "%25". When converting
+/// the URL back to a string, '%' is never encoded and only characters which are not allowed in
+/// an URL are percent-encoded, so that parsing and converting back an URL never changes it nor
+/// encodes it twice. The authority (host and port) is never encoded, except for
+/// '?' and '#'. A query or fragment included in the path given to [new](Url::new) is kept as is,
+/// unless a query or fragment is set with [with_query](Url::with_query) or
+/// [with_fragment](Url::with_fragment): in that case '?' and '#' in the path are percent-encoded
+/// such that they cannot be confused with the query or fragment.
 pub struct Url<'a> {
     scheme: &'a str,
-    path: &'a OsStr
+    path: &'a OsStr,
+    query: Option<&'a str>,
+    fragment: Option<&'a str>
+}
+
+fn is_pchar(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=:@/".contains(&b)
+}
+
+fn is_path_char(b: u8) -> bool {
+    //'?' and '#' are kept such that a query or fragment given in the path of Url::new is
+    // preserved.
+    is_pchar(b) || b"?#[]".contains(&b)
+}
+
+fn is_path_char_with_fragment(b: u8) -> bool {
+    is_pchar(b) || b"?[]".contains(&b)
+}
+
+fn is_path_char_with_query(b: u8) -> bool {
+    is_pchar(b) || b"[]".contains(&b)
+}
+
+fn is_fragment_char(b: u8) -> bool {
+    is_pchar(b) || b == b'?'
+}
+
+fn percent_encode(value: &str, is_allowed: fn(u8) -> bool) -> String {
+    let mut s = String::with_capacity(value.len());
+    for b in value.bytes() {
+        //Components are already percent-encoded, so '%' is kept as is to avoid double encoding.
+        if is_allowed(b) || b == b'%' {
+            s.push(b as char);
+        } else {
+            s.push_str(&format!("%{:02X}", b));
+        }
+    }
+    s
+}
+
+fn encode_path(path: &str, query: bool, fragment: bool) -> String {
+    //A '?' or '#' in the path would be read as the start of the query or fragment once one is
+    // appended, so they must be encoded in that case.
+    let is_allowed: fn(u8) -> bool = match (query, fragment) {
+        (true, _) => is_path_char_with_query,
+        (false, true) => is_path_char_with_fragment,
+        (false, false) => is_path_char
+    };
+    //The authority (user info, host and port) is otherwise kept as is: it contains characters
+    // such as '[' and ']' for IPv6 hosts which must not be encoded.
+    let (authority, path) = match path.find('/') {
+        Some(id) => path.split_at(id),
+        None => (path, "")
+    };
+    let mut s = String::with_capacity(authority.len() + path.len());
+    for c in authority.chars() {
+        match c {
+            '?' if query => s.push_str("%3F"),
+            '#' if query || fragment => s.push_str("%23"),
+            _ => s.push(c)
+        }
+    }
+    s.push_str(&percent_encode(path, is_allowed));
+    s
 }
 
 impl<'a> Url<'a> {
@@ -57,7 +130,7 @@ impl<'a> Url<'a> {
     /// # Arguments
     ///
     /// * `scheme`: the URL scheme.
-    /// * `path`: the URL path.
+    /// * `path`: the percent-encoded URL path.
     ///
     /// returns: Url
     ///
@@ -71,7 +144,55 @@ impl<'a> Url<'a> {
     /// assert_eq!(url.path(), OsStr::new("rust-lang.org"));
     /// ```
     pub fn new(scheme: &'a str, path: &'a OsStr) -> Url<'a> {
-        Url { scheme, path }
+        Url { scheme, path, query: None, fragment: None }
+    }
+
+    /// Sets the query of this URL.
+    ///
+    /// The query is ignored for URLs which are paths to a file or a folder on the local system.
+    ///
+    /// # Arguments
+    ///
+    /// * `query`: the percent-encoded URL query without the leading '?'.
+    ///
+    /// returns: Url
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::ffi::OsStr;
+    /// use bp3d_os::open::Url;
+    /// let url = Url::new("https", OsStr::new("crates.io/search")).with_query("q=bp3d");
+    /// assert_eq!(url.query(), Some("q=bp3d"));
+    /// assert_eq!(&url.to_os_str().unwrap(), OsStr::new("https://crates.io/search?q=bp3d"));
+    /// ```
+    pub fn with_query(mut self, query: &'a str) -> Url<'a> {
+        self.query = Some(query);
+        self
+    }
+
+    /// Sets the fragment (anchor) of this URL.
+    ///
+    /// The fragment is ignored for URLs which are paths to a file or a folder on the local system.
+    ///
+    /// # Arguments
+    ///
+    /// * `fragment`: the percent-encoded URL fragment without the leading '#'.
+    ///
+    /// returns: Url
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::ffi::OsStr;
+    /// use bp3d_os::open::Url;
+    /// let url = Url::new("https", OsStr::new("rust-lang.org")).with_fragment("learn");
+    /// assert_eq!(url.fragment(), Some("learn"));
+    /// assert_eq!(&url.to_os_str().unwrap(), OsStr::new("https://rust-lang.org#learn"));
+    /// ```
+    pub fn with_fragment(mut self, fragment: &'a str) -> Url<'a> {
+        self.fragment = Some(fragment);
+        self
     }
 
     /// Returns the scheme of this URL.
@@ -80,10 +201,26 @@ impl<'a> Url<'a> {
     }
 
     /// Returns the path of this URL.
+    ///
+    /// The path is returned as it was given, in its percent-encoded form.
     pub fn path(&self) -> &'a OsStr {
         self.path
     }
 
+    /// Returns the query of this URL if any.
+    ///
+    /// The query is returned as it was given, in its percent-encoded form.
+    pub fn query(&self) -> Option<&'a str> {
+        self.query
+    }
+
+    /// Returns the fragment (anchor) of this URL if any.
+    ///
+    /// The fragment is returned as it was given, in its percent-encoded form.
+    pub fn fragment(&self) -> Option<&'a str> {
+        self.fragment
+    }
+
     /// Returns true if this URL is a path to a file or a folder on the local system.
     pub fn is_path(&self) -> bool {
         self.scheme == "file"
@@ -111,7 +248,18 @@ impl<'a> Url<'a> {
                 s.push(path);
             }
         } else {
-            s.push(self.path);
+            match self.path.to_str() {
+                Some(path) => s.push(encode_path(path, self.query.is_some(), self.fragment.is_some())),
+                None => s.push(self.path)
+            }
+            if let Some(query) = self.query {
+                s.push("?");
+                s.push(percent_encode(query, is_fragment_char));
+            }
+            if let Some(fragment) = self.fragment {
+                s.push("#");
+                s.push(percent_encode(fragment, is_fragment_char));
+            }
         }
         Ok(s)
     }
@@ -119,7 +267,21 @@ impl<'a> Url<'a> {
 
 impl<'a> Display for Url<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}://{}", self.scheme, self.path.to_string_lossy())
+        if self.is_path() {
+            return write!(f, "{}://{}", self.scheme, self.path.to_string_lossy());
+        }
+        write!(f, "{}://{}", self.scheme, encode_path(
+            &self.path.to_string_lossy(),
+            self.query.is_some(),
+            self.fragment.is_some()
+        ))?;
+        if let Some(query) = self.query {
+            write!(f, "?{}", percent_encode(query, is_fragment_char))?;
+        }
+        if let Some(fragment) = self.fragment {
+            write!(f, "#{}", percent_encode(fragment, is_fragment_char))?;
+        }
+        Ok(())
     }
 }
 
@@ -136,10 +298,132 @@ impl<'a> TryFrom<&'a str> for Url<'a> {
         match value.find("://") {
             Some(id) => {
                 let scheme = &value[..id];
-                let path = &value[id + 3..];
-                Ok(Url { scheme, path: path.as_ref() })
+                let mut path = &value[id + 3..];
+                let mut query = None;
+                let mut fragment = None;
+                //'?' and '#' are valid file name characters so only look for queries and
+                // fragments in non-file URLs.
+                if scheme != "file" {
+                    if let Some(id) = path.find('#') {
+                        fragment = Some(&path[id + 1..]);
+                        path = &path[..id];
+                    }
+                    if let Some(id) = path.find('?') {
+                        query = Some(&path[id + 1..]);
+                        path = &path[..id];
+                    }
+                }
+                Ok(Url { scheme, path: path.as_ref(), query, fragment })
             },
             None => Err(InvalidUrl(value))
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::open::Url;
+    use std::convert::TryFrom;
+    use std::ffi::OsStr;
+    use std::path::Path;
+
+    #[test]
+    fn fragment() {
+        let url = Url::try_from("https://example.com/page#section").unwrap();
+        assert_eq!(url.scheme(), "https");
+        assert_eq!(url.path(), OsStr::new("example.com/page"));
+        assert_eq!(url.fragment(), Some("section"));
+        assert_eq!(&url.to_os_str().unwrap(), OsStr::new("https://example.com/page#section"));
+        assert_eq!(url.to_string(), "https://example.com/page#section");
+    }
+
+    #[test]
+    fn fragment_encoding() {
+        let url = Url::new("https", OsStr::new("example.com")).with_fragment("my section%20é");
+        assert_eq!(&url.to_os_str().unwrap(), OsStr::new("https://example.com#my%20section%20%C3%A9"));
+        let url = Url::try_from("https://example.com#a%20b").unwrap();
+        assert_eq!(url.fragment(), Some("a%20b"));
+        assert_eq!(&url.to_os_str().unwrap(), OsStr::new("https://example.com#a%20b"));
+    }
+
+    #[test]
+    fn fragment_file() {
+        let url = Url::try_from("file:///tmp/file?#1.txt").unwrap();
+        assert_eq!(url.path(), OsStr::new("/tmp/file?#1.txt"));
+        assert_eq!(url.query(), None);
+        assert_eq!(url.fragment(), None);
+        let url = Url::from(Path::new("/tmp")).with_query("a=b").with_fragment("section");
+        assert_eq!(&url.to_os_str().unwrap(), OsStr::new("file:///tmp"));
+        assert_eq!(url.to_string(), "file:///tmp");
+    }
+
+    #[test]
+    fn round_trip() {
+        let urls = [
+            "https://example.com/my%20page",
+            "https://example.com/search?a=b&c=d%20e",
+            "https://example.com/page#c",
+            "https://example.com/my%20page?a=b#c%20d",
+            "https://example.com/page#c?d",
+            "https://example.com/search?q=a%26b%3Dc",
+            "https://example.com/a%2Fb",
+            "https://example.com/search?q=1%2B1",
+            "https://example.com/100%?a=5%#b%",
+        ];
+        for str in urls.iter() {
+            let url = Url::try_from(*str).unwrap();
+            assert_eq!(&url.to_os_str().unwrap(), OsStr::new(str));
+            assert_eq!(&url.to_string(), str);
+        }
+        let url = Url::try_from("https://example.com/my%20page?a=b#c").unwrap();
+        assert_eq!(url.path(), OsStr::new("example.com/my%20page"));
+        assert_eq!(url.query(), Some("a=b"));
+        assert_eq!(url.fragment(), Some("c"));
+        let url = Url::try_from("https://example.com/page#c?d").unwrap();
+        assert_eq!(url.query(), None);
+        assert_eq!(url.fragment(), Some("c?d"));
+    }
+
+    #[test]
+    fn path_encoding() {
+        let url = Url::new("https", OsStr::new("example.com/my page")).with_query("a b");
+        assert_eq!(&url.to_os_str().unwrap(), OsStr::new("https://example.com/my%20page?a%20b"));
+        let url = Url::new("https", OsStr::new("example.com/my%20page?q=a%26b"));
+        assert_eq!(url.to_string(), "https://example.com/my%20page?q=a%26b");
+    }
+
+    #[test]
+    fn authority() {
+        let urls = [
+            "http://[::1]:8080/index.html",
+            "http://[::1]",
+            "https://user@example.com:8443/my%20page",
+        ];
+        for str in urls.iter() {
+            let url = Url::try_from(*str).unwrap();
+            assert_eq!(&url.to_os_str().unwrap(), OsStr::new(str));
+            assert_eq!(&url.to_string(), str);
+        }
+    }
+
+    #[test]
+    fn embedded_query() {
+        let url = Url::new("https", OsStr::new("example.com/search?q=rust#results"));
+        assert_eq!(url.query(), None);
+        assert_eq!(&url.to_os_str().unwrap(), OsStr::new("https://example.com/search?q=rust#results"));
+        assert_eq!(url.to_string(), "https://example.com/search?q=rust#results");
+    }
+
+    #[test]
+    fn reserved_chars_in_path() {
+        let url = Url::new("https", OsStr::new("host/a#b")).with_fragment("c");
+        assert_eq!(url.to_string(), "https://host/a%23b#c");
+        assert_eq!(&url.to_os_str().unwrap(), OsStr::new("https://host/a%23b#c"));
+        let url = Url::new("https", OsStr::new("host/a?b")).with_query("c");
+        assert_eq!(url.to_string(), "https://host/a%3Fb?c");
+        let url = Url::new("https", OsStr::new("host/search?q=x")).with_fragment("f");
+        assert_eq!(url.to_string(), "https://host/search?q=x#f");
+        let url = Url::new("https", OsStr::new("host#b")).with_fragment("c");
+        assert_eq!(url.to_string(), "https://host%23b#c");
+    }
+}
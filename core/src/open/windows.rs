@@ -26,17 +26,29 @@
 // NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
 // SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use crate::open::Url;
+use crate::open::{OpenVerb, Url};
 use std::os::windows::ffi::OsStrExt;
+use std::os::windows::process::CommandExt;
 use std::path::Path;
+use std::process::Command;
 use windows_sys::Win32::UI::Shell::ShellExecuteW;
 use windows_sys::Win32::UI::WindowsAndMessaging::SW_SHOW;
+use windows_sys::Win32::System::Threading::CREATE_NEW_CONSOLE;
 use windows_sys::core::PCWSTR;
 use crate::fs::PathExt;
 
-pub fn open(url: &Url) -> bool {
+fn verb_string(verb: OpenVerb) -> &'static str {
+    match verb {
+        OpenVerb::Open => "open",
+        OpenVerb::Edit => "edit",
+        OpenVerb::Print => "print",
+        OpenVerb::Explore => "explore",
+    }
+}
+
+fn shell_execute(url: &Url, verb: &str) -> bool {
     unsafe {
-        let operation = ['o' as u16, 'p' as u16, 'e' as u16, 'n' as u16, 0x0000];
+        let operation: Vec<u16> = verb.encode_utf16().chain(std::iter::once(0x0000)).collect();
         let mut urlw: Vec<u16> = match url.is_path() {
             true => {
                 let path = match Path::new(url.path()).get_absolute() {
@@ -60,6 +72,44 @@ pub fn open(url: &Url) -> bool {
     }
 }
 
+pub fn open(url: &Url) -> bool {
+    shell_execute(url, verb_string(OpenVerb::Open))
+}
+
+pub fn open_with_verb(url: &Url, verb: OpenVerb) -> bool {
+    shell_execute(url, verb_string(verb))
+}
+
+pub fn open_all(urls: &[Url]) -> bool {
+    //ShellExecuteW has no batch variant; every URL is still attempted when one fails.
+    urls.iter().filter(|v| !open(v)).count() == 0
+}
+
 pub fn show_in_files<'a, I: Iterator<Item = &'a Path>>(_: I) -> bool {
     false
 }
+
+pub fn open_terminal(dir: &Path) -> bool {
+    if Command::new("wt").arg("-d").arg(dir).spawn().is_ok() {
+        return true;
+    }
+    Command::new("cmd")
+        .current_dir(dir)
+        .creation_flags(CREATE_NEW_CONSOLE)
+        .spawn()
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::verb_string;
+    use crate::open::OpenVerb;
+
+    #[test]
+    fn verbs() {
+        assert_eq!(verb_string(OpenVerb::Open), "open");
+        assert_eq!(verb_string(OpenVerb::Edit), "edit");
+        assert_eq!(verb_string(OpenVerb::Print), "print");
+        assert_eq!(verb_string(OpenVerb::Explore), "explore");
+    }
+}